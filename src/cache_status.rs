@@ -0,0 +1,95 @@
+//! Parsing of the Cache-Status header (RFC 9211 <https://tools.ietf.org/html/rfc9211>), which
+//! caches use to report how they handled a request. It is handy to correlate with Cache-Control
+//! when debugging proxies.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{split_directives, split_params, unquote};
+
+/// One cache's entry in a Cache-Status header. Only the common parameters are parsed.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct CacheStatusEntry {
+    /// The name of the cache that added the entry.
+    pub cache: String,
+
+    /// Whether the request was served from the cache (`hit`).
+    pub hit: bool,
+
+    /// Why the request was forwarded towards the origin (`fwd`), e.g. `"uri-miss"`.
+    pub fwd: Option<String>,
+
+    /// The remaining freshness lifetime in seconds (`ttl`). Negative once stale.
+    pub ttl: Option<i64>,
+}
+
+/// Parses the value of the Cache-Status header (i.e. everything after "Cache-Status:"). Entries
+/// are returned in the order given, which goes from the origin towards the client. Unknown or
+/// malformed parameters are ignored.
+pub fn parse_cache_status(value: &str) -> Vec<CacheStatusEntry> {
+    let mut entries = Vec::new();
+    for member in split_directives(value) {
+        let mut params = split_params(member).map(|s| s.trim());
+        let cache = params.next().unwrap();
+        if cache.is_empty() {
+            continue;
+        }
+        let mut entry = CacheStatusEntry {
+            cache: unquote(cache),
+            ..CacheStatusEntry::default()
+        };
+        for param in params {
+            let mut key_value = param.splitn(2, '=').map(|s| s.trim());
+            let key = key_value.next().unwrap();
+            let val = key_value.next();
+            match key {
+                "hit" => entry.hit = val.is_none() || val == Some("?1"),
+                "fwd" => entry.fwd = val.map(unquote),
+                "ttl" => entry.ttl = val.and_then(|val| val.parse().ok()),
+                _ => (),
+            }
+        }
+        entries.push(entry);
+    }
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cache_status, CacheStatusEntry};
+
+    #[test]
+    fn test_parse_cache_status() {
+        assert_eq!(
+            parse_cache_status("OriginCache; hit; ttl=1100, \"CDN Company Here\"; fwd=uri-miss"),
+            vec![
+                CacheStatusEntry {
+                    cache: String::from("OriginCache"),
+                    hit: true,
+                    fwd: None,
+                    ttl: Some(1100),
+                },
+                CacheStatusEntry {
+                    cache: String::from("CDN Company Here"),
+                    hit: false,
+                    fwd: Some(String::from("uri-miss")),
+                    ttl: None,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_cache_status("ExampleCache; hit; ttl=-30")[0].ttl,
+            Some(-30)
+        );
+        assert!(parse_cache_status("").is_empty());
+        assert_eq!(
+            parse_cache_status("\"a;b\"; hit; fwd=\"x;y\""),
+            vec![CacheStatusEntry {
+                cache: String::from("a;b"),
+                hit: true,
+                fwd: Some(String::from("x;y")),
+                ttl: None,
+            }]
+        );
+    }
+}
@@ -1,7 +1,33 @@
-use std::time::Duration;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "time")]
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Range;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "cache-status")]
+mod cache_status;
+
+#[cfg(feature = "cache-status")]
+pub use cache_status::{parse_cache_status, CacheStatusEntry};
+
+#[cfg(feature = "headers")]
+mod typed_header;
+
+#[cfg(feature = "headers")]
+pub use typed_header::CacheControlHeader;
 
 /// How the data may be cached.
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum Cachability {
     /// Any cache can cache this data.
     Public,
@@ -16,6 +42,176 @@ pub enum Cachability {
     OnlyIfCached,
 }
 
+impl Cachability {
+    fn directive(&self) -> &'static str {
+        match self {
+            Cachability::Public => "public",
+            Cachability::Private => "private",
+            Cachability::NoCache => "no-cache",
+            Cachability::OnlyIfCached => "only-if-cached",
+        }
+    }
+}
+
+/// How strictly a Cache-Control value is parsed.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ParseMode {
+    /// Only accept values that follow the RFCs. This is what `CacheControl::from_value` uses.
+    Strict,
+
+    /// Also accept common non-compliant forms sent by real servers, such as `max-age="60"`,
+    /// `max_age=60`, `maxage=60` or `.max-age=60`, directives separated by line breaks, and a
+    /// leading byte order mark.
+    Lenient,
+
+    /// Parse like `Strict`, but have `CacheControl::parse` only fail on unknown directives that
+    /// carry a value, such as `maxage=5`, which are likely typos of real ones. Unknown bare
+    /// directives like `flag-x` may be future extensions and are reported in
+    /// `ParseReport::ignored`.
+    RejectUnknownValued,
+}
+
+/// The kind of cache a policy is being applied by.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum CacheRole {
+    /// A cache shared between users, such as a proxy or CDN.
+    Shared,
+
+    /// A cache dedicated to a single user, such as a browser cache.
+    Private,
+}
+
+/// Whether `max-age` is set, and if so whether it is zero.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum MaxAgeState {
+    /// There is no `max-age` directive.
+    Absent,
+
+    /// `max-age=0`: the response is stale as soon as it is received.
+    Zero,
+
+    /// `max-age` with a positive number of seconds.
+    Positive(Duration),
+}
+
+/// When a shared cache has to revalidate a stored response. Returned by
+/// `CacheControl::shared_revalidation_policy`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum SharedRevalidation {
+    /// No directive ever makes the response stale. Any freshness is left to heuristics.
+    NeverStale,
+
+    /// The response is fresh for this long, and must be revalidated afterwards.
+    RevalidateAfter(Duration),
+
+    /// The response must be revalidated before every use.
+    AlwaysRevalidate,
+}
+
+/// What a cache may do with a stored response. Returned by `CacheControl::reuse_decision`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ReuseDecision {
+    /// The response is fresh and can be served as is.
+    Fresh,
+
+    /// The response has to be revalidated with the origin before it is served.
+    StaleRevalidate,
+
+    /// The stale response can be served while it is revalidated in the background (RFC 5861).
+    ServeStaleWhileRevalidate,
+
+    /// The origin failed, and the stale response can be served instead (RFC 5861).
+    ServeStaleOnError,
+
+    /// The response must not be served from the cache at all.
+    MustNotReuse,
+}
+
+/// The result of `CacheControl::parse`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseReport {
+    pub cache_control: CacheControl,
+
+    /// The unknown directives that were skipped, as written in the value. Always empty in strict
+    /// mode, which rejects them, and only holds bare directives with `RejectUnknownValued`.
+    pub ignored: Vec<String>,
+
+    /// The known directives that appear more than once, of which only the last takes effect.
+    /// Always empty outside lenient mode, which rejects them.
+    pub duplicates: Vec<String>,
+}
+
+/// Why `CacheControl::parse` rejected a value.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// A directive this crate does not know, with its name as written.
+    UnknownDirective(String),
+
+    /// A known directive whose value is missing or malformed.
+    InvalidValue(String),
+
+    /// A known directive that appears more than once.
+    DuplicateDirective(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownDirective(name) => write!(f, "unknown directive {}", name),
+            ParseError::InvalidValue(name) => write!(f, "invalid value for {}", name),
+            ParseError::DuplicateDirective(name) => write!(f, "duplicate directive {}", name),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Two directives of a policy that contradict each other.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct ConflictError {
+    pub first: &'static str,
+    pub second: &'static str,
+}
+
+impl fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} with {}", self.first, self.second)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConflictError {}
+
+/// How serious a problem reported by `CacheControl::lint` is.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Severity {
+    /// The value is valid, but probably doesn't do what was intended.
+    Warning,
+
+    /// The value is malformed.
+    Error,
+}
+
+/// A directive that differs between two policies. Returned by `CacheControl::diff`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FieldChange {
+    pub directive: &'static str,
+    /// The directive as formatted in the old policy's header value, or `None` if it wasn't set.
+    pub old: Option<String>,
+    /// The directive as formatted in the new policy's header value, or `None` if it isn't set.
+    pub new: Option<String>,
+}
+
+/// A problem found by `CacheControl::lint`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The byte range of the offending directive in the linted value.
+    pub range: Range<usize>,
+}
+
 /// Represents a Cache-Control header
 /// # Example
 /// ```
@@ -28,13 +224,16 @@ pub enum Cachability {
 /// assert_eq!(cache_control.max_age, Some(Duration::new(60, 0)));
 /// ```
 ///
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
 pub struct CacheControl {
     pub cachability: Option<Cachability>,
     pub max_age: Option<Duration>,
     pub s_max_age: Option<Duration>,
     pub max_stale: Option<Duration>,
     pub min_fresh: Option<Duration>,
+    /// The field names listed in `no-cache="..."`, with their casing as sent. Empty for a bare
+    /// `no-cache`. Field names are case-insensitive, so compare them with `is_no_cache_field`.
+    pub no_cache_fields: Vec<String>,
     pub must_revalidate: bool,
     pub proxy_revalidate: bool,
     pub immutable: bool,
@@ -44,6 +243,10 @@ pub struct CacheControl {
     // RFC 5861 https://tools.ietf.org/html/rfc5861
     pub stale_while_revalidate: Option<Duration>,
     pub stale_if_error: Option<Duration>,
+
+    /// The directives this crate doesn't know, such as those of newer drafts, as `(name, value)`
+    /// pairs in the order they appear. Values are kept as written, including any quotes.
+    pub extensions: Vec<(String, Option<String>)>,
 }
 
 impl CacheControl {
@@ -51,53 +254,214 @@ impl CacheControl {
         CacheControl::default()
     }
 
+    /// Sets the cachability directive. The `no-cache` field names only belong to the directive
+    /// they came with, so they are cleared.
+    fn set_cachability(&mut self, cachability: Cachability) {
+        self.cachability = Some(cachability);
+        self.no_cache_fields.clear();
+    }
+
     /// Parses the value of the Cache-Control header (i.e. everything after "Cache-Control:").
+    ///
+    /// Empty directives are skipped, so a value that is empty, whitespace-only or made up only of
+    /// commas (e.g. `",,,"`) yields `Some(CacheControl::default())` rather than `None`. A directive
+    /// that appears more than once, as in `max-age=60, max-age=120`, takes its last value; use
+    /// `parse` to detect this.
     pub fn from_value(value: &str) -> Option<CacheControl> {
+        CacheControl::from_value_with_mode(value, ParseMode::Strict)
+    }
+
+    /// Parses the value of the Cache-Control header like `from_value`, falling back to
+    /// `CacheControl::default()` when it is malformed.
+    pub fn from_value_or_default(value: &str) -> CacheControl {
+        CacheControl::from_value(value).unwrap_or_default()
+    }
+
+    /// Parses the value of the Cache-Control header from a borrowed or owned string, such as the
+    /// `Cow<str>` returned by header decoding. Behaves like `from_value`.
+    pub fn from_value_cow<'a>(value: impl Into<Cow<'a, str>>) -> Option<CacheControl> {
+        CacheControl::from_value(&value.into())
+    }
+
+    /// Parses the value of the Cache-Control header using the given `ParseMode`.
+    pub fn from_value_with_mode(value: &str, mode: ParseMode) -> Option<CacheControl> {
+        CacheControl::parse_directives(value, mode, None)
+            .ok()
+            .map(|report| report.cache_control)
+    }
+
+    /// Parses the value of the Cache-Control header like `from_value`, but caps every delta-seconds
+    /// value at 2147483647 (2^31 - 1) seconds instead of rejecting values that don't fit in a
+    /// `u64`, as RFC 9111 advises.
+    pub fn from_value_rfc_capped(value: &str) -> Option<CacheControl> {
+        CacheControl::parse_directives(value, ParseMode::Strict, Some(2_147_483_647))
+            .ok()
+            .map(|report| report.cache_control)
+    }
+
+    /// Parses the value of the Cache-Control header like `from_value`, but returns `None` without
+    /// parsing anything when it has more than `max_directives` directives. Empty directives are
+    /// not counted.
+    pub fn from_value_limited(value: &str, max_directives: usize) -> Option<CacheControl> {
+        let count = split_directives(value)
+            .filter(|directive| !directive.trim().is_empty())
+            .take(max_directives.saturating_add(1))
+            .count();
+        if count > max_directives {
+            return None;
+        }
+        CacheControl::from_value(value)
+    }
+
+    /// Parses the value of the Cache-Control header, reporting what was not understood.
+    ///
+    /// `from_value` ignores unknown directives, as RFC 9111 requires of caches, only keeping them
+    /// in `extensions`. Here they are reported instead: strict mode fails with
+    /// `ParseError::UnknownDirective` while lenient mode lists them in `ParseReport::ignored`, and
+    /// `ParseMode::RejectUnknownValued` only fails on those with a value. Malformed values of known
+    /// directives fail with `ParseError::InvalidValue` in every mode.
+    ///
+    /// Repeated known directives usually indicate a bug. Lenient mode lists them in
+    /// `ParseReport::duplicates`, and the other modes fail with `ParseError::DuplicateDirective`.
+    pub fn parse(value: &str, mode: ParseMode) -> Result<ParseReport, ParseError> {
+        let report = CacheControl::parse_directives(value, mode, None)?;
+        CacheControl::check_report(report, mode)
+    }
+
+    /// Fails with what `mode` rejects among the unknown and duplicate directives of `report`.
+    fn check_report(report: ParseReport, mode: ParseMode) -> Result<ParseReport, ParseError> {
+        let rejected = match mode {
+            ParseMode::Strict => report.ignored.first(),
+            ParseMode::Lenient => None,
+            ParseMode::RejectUnknownValued => report.ignored.iter().find(|d| d.contains('=')),
+        };
+        if let Some(rejected) = rejected {
+            let name = rejected.split('=').next().unwrap().trim();
+            return Err(ParseError::UnknownDirective(String::from(name)));
+        }
+        if mode != ParseMode::Lenient {
+            if let Some(duplicate) = report.duplicates.first() {
+                return Err(ParseError::DuplicateDirective(duplicate.clone()));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Builds a policy from directives that were already split, such as `["public",
+    /// "max-age=60"]`, matching each like `from_value` does.
+    pub fn from_tokens<'a, S>(tokens: impl IntoIterator<Item = &'a S>) -> Option<CacheControl>
+    where
+        S: AsRef<str> + ?Sized + 'a,
+    {
+        let tokens = tokens.into_iter().map(|token| token.as_ref());
+        CacheControl::parse_tokens(tokens, ParseMode::Strict, None)
+            .ok()
+            .map(|report| report.cache_control)
+    }
+
+    fn parse_directives(
+        value: &str,
+        mode: ParseMode,
+        cap: Option<u64>,
+    ) -> Result<ParseReport, ParseError> {
+        let strict = mode != ParseMode::Lenient;
+        // Values copied from some tools start with a byte order mark, which `trim` keeps.
+        let value = if strict {
+            value
+        } else {
+            value.trim_start_matches(|c: char| c == '\u{feff}' || c.is_whitespace())
+        };
+        let tokens = if strict {
+            split_directives(value)
+        } else {
+            split_directives_and_lines(value)
+        };
+        CacheControl::parse_tokens(tokens, mode, cap)
+    }
+
+    /// `cap`, when given, is the largest delta-seconds value; larger ones are lowered to it.
+    fn parse_tokens<'a>(
+        tokens: impl Iterator<Item = &'a str>,
+        mode: ParseMode,
+        cap: Option<u64>,
+    ) -> Result<ParseReport, ParseError> {
+        let strict = mode != ParseMode::Lenient;
         let mut ret = CacheControl::new();
-        let tokens: Vec<&str> = value.split(",").collect();
+        let mut ignored = Vec::new();
+        let mut seen = Vec::new();
+        let mut duplicates = Vec::new();
+        let mut previous_seconds: Option<&str> = None;
         for token in tokens {
-            let key_value: Vec<&str> = token.split("=").map(|s| s.trim()).collect();
-            let key = key_value.first().unwrap();
-            let val = key_value.get(1);
-
-            match *key {
-                "public" => ret.cachability = Some(Cachability::Public),
-                "private" => ret.cachability = Some(Cachability::Private),
-                "no-cache" => ret.cachability = Some(Cachability::NoCache),
-                "only-if-cached" => ret.cachability = Some(Cachability::OnlyIfCached),
-                "max-age" => {
-                    if let None = val {
-                        return None;
-                    }
-                    let val_d = *(val.unwrap());
-                    let p_val = val_d.parse();
-                    if let Err(_) = p_val {
-                        return None;
-                    }
-                    ret.max_age = Some(Duration::new(p_val.unwrap(), 0));
+            let mut key_value = token.splitn(2, '=').map(|s| s.trim());
+            let mut key = key_value.next().unwrap();
+            let val = key_value.next();
+            if !strict {
+                // No directive name starts or ends with punctuation, so it must be stray.
+                key = lenient_alias(key.trim_matches(|c: char| c.is_ascii_punctuation()));
+            }
+            // Delta-seconds can't be quoted, but lenient mode accepts `max-age="60"`.
+            let seconds = if strict { val } else { val.map(strip_quotes) };
+            let invalid = || ParseError::InvalidValue(String::from(key));
+            let delta_seconds = || {
+                match cap {
+                    Some(cap) => require_capped_seconds(seconds, cap),
+                    None => require_seconds(seconds),
                 }
-                "max-stale" => {
-                    if let None = val {
-                        return None;
-                    }
-                    let val_d = *(val.unwrap());
-                    let p_val = val_d.parse();
-                    if let Err(_) = p_val {
-                        return None;
-                    }
-                    ret.max_stale = Some(Duration::new(p_val.unwrap(), 0));
+                .map_err(|()| invalid())
+            };
+
+            // `max-age=1,000` would otherwise silently be read as `max-age=1`.
+            if strict && val.is_none() && is_digits(key) {
+                if let Some(previous) = previous_seconds {
+                    return Err(ParseError::InvalidValue(String::from(previous)));
                 }
-                "min-fresh" => {
-                    if let None = val {
-                        return None;
-                    }
-                    let val_d = *(val.unwrap());
-                    let p_val = val_d.parse();
-                    if let Err(_) = p_val {
-                        return None;
+            }
+            previous_seconds = if takes_delta_seconds(key) {
+                Some(key)
+            } else {
+                None
+            };
+
+            // Boolean directives take no value. Lenient mode ignores one, as in `must-revalidate=1`.
+            let boolean = matches!(
+                key,
+                "public"
+                    | "only-if-cached"
+                    | "must-revalidate"
+                    | "proxy-revalidate"
+                    | "immutable"
+                    | "no-store"
+                    | "no-transform"
+            );
+            if strict && boolean && val.is_some() {
+                return Err(invalid());
+            }
+
+            match key {
+                "public" => ret.set_cachability(Cachability::Public),
+                "private" => ret.set_cachability(Cachability::Private),
+                "no-cache" => {
+                    // `no-cache=` with nothing after it is malformed. Lenient mode reads it as a
+                    // bare `no-cache`.
+                    if strict && val == Some("") {
+                        return Err(invalid());
                     }
-                    ret.min_fresh = Some(Duration::new(p_val.unwrap(), 0));
+                    ret.cachability = Some(Cachability::NoCache);
+                    ret.no_cache_fields = match val {
+                        Some(val) => unquote(val)
+                            .split(',')
+                            .map(|s| s.trim())
+                            .filter(|s| !s.is_empty())
+                            .map(String::from)
+                            .collect(),
+                        None => Vec::new(),
+                    };
                 }
+                "only-if-cached" => ret.set_cachability(Cachability::OnlyIfCached),
+                "max-age" => ret.max_age = Some(delta_seconds()?),
+                "s-maxage" => ret.s_max_age = Some(delta_seconds()?),
+                "max-stale" => ret.max_stale = Some(delta_seconds()?),
+                "min-fresh" => ret.min_fresh = Some(delta_seconds()?),
                 "must-revalidate" => ret.must_revalidate = true,
                 "proxy-revalidate" => ret.proxy_revalidate = true,
                 "immutable" => ret.immutable = true,
@@ -105,220 +469,4134 @@ impl CacheControl {
                 "no-transform" => ret.no_transform = true,
 
                 // RFC 5861 https://tools.ietf.org/html/rfc5861
-                "stale-while-revalidate" => {
-                    if let None = val {
-                        return None;
-                    }
-                    let val_d = *(val.unwrap());
-                    let p_val = val_d.parse();
-                    if let Err(_) = p_val {
-                        return None;
-                    }
-                    ret.stale_while_revalidate = Some(Duration::new(p_val.unwrap(), 0));
-                }
-                "stale-if-error" => {
-                    if let None = val {
-                        return None;
-                    }
-                    let val_d = *(val.unwrap());
-                    let p_val = val_d.parse();
-                    if let Err(_) = p_val {
-                        return None;
-                    }
-                    ret.stale_if_error = Some(Duration::new(p_val.unwrap(), 0));
+                "stale-while-revalidate" => ret.stale_while_revalidate = Some(delta_seconds()?),
+                "stale-if-error" => ret.stale_if_error = Some(delta_seconds()?),
+                "" => continue,
+                _ => {
+                    ignored.push(String::from(token.trim()));
+                    ret.extensions
+                        .push((String::from(key), val.map(String::from)));
+                    continue;
                 }
-                _ => (),
             };
+
+            if !seen.contains(&key) {
+                seen.push(key);
+            } else if !duplicates.iter().any(|duplicate| duplicate == key) {
+                duplicates.push(String::from(key));
+            }
         }
-        Some(ret)
+        Ok(ParseReport {
+            cache_control: ret,
+            ignored,
+            duplicates,
+        })
     }
 
-    /// Parses a Cache-Control header.
+    /// Parses a Cache-Control header. Values folded across lines (obs-fold) are unfolded first.
+    /// Only the first colon separates the name from the value, so quoted values may contain colons.
     pub fn from_header(value: &str) -> Option<CacheControl> {
-        let header_value: Vec<&str> = value.split(":").map(|s| s.trim()).collect();
-        if header_value.len() != 2 || header_value.first().unwrap() != &"Cache-Control" {
+        let value = unfold(value);
+        let mut header_value = value.splitn(2, ':').map(|s| s.trim());
+        let name = header_value.next().unwrap();
+        let val = header_value.next()?;
+        if name != "Cache-Control" {
             return None;
         }
-        let val = header_value.get(1).unwrap();
         CacheControl::from_value(val)
     }
-}
 
-impl Default for CacheControl {
-    fn default() -> Self {
-        CacheControl {
-            cachability: None,
-            max_age: None,
-            s_max_age: None,
-            max_stale: None,
-            min_fresh: None,
-            must_revalidate: false,
-            proxy_revalidate: false,
-            immutable: false,
-            no_store: false,
-            no_transform: false,
-
-            // RFC 5861 https://tools.ietf.org/html/rfc5861
-            stale_while_revalidate: None,
-            stale_if_error: None,
+    /// Parses the Cache-Control headers of a raw header block, one header per line, combined as if
+    /// they were a single comma-separated value. Header names are matched case-insensitively, and
+    /// lines without a colon, such as a status line, are skipped. Returns `None` when there is no
+    /// Cache-Control header.
+    pub fn from_headers_block(block: &str) -> Option<CacheControl> {
+        let block = unfold(block);
+        let values: Vec<&str> = block
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(name, _)| name.trim().eq_ignore_ascii_case("Cache-Control"))
+            .map(|(_, value)| value.trim())
+            .collect();
+        if values.is_empty() {
+            return None;
         }
+        CacheControl::from_value(&values.join(", "))
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::{Cachability, CacheControl};
-    use std::time::Duration;
 
-    #[test]
-    fn test_from_value() {
-        assert_eq!(
-            CacheControl::from_value("").unwrap(),
-            CacheControl::default()
-        );
-        assert_eq!(
-            CacheControl::from_value("private")
-                .unwrap()
-                .cachability
-                .unwrap(),
-            Cachability::Private
-        );
-        assert_eq!(
-            CacheControl::from_value("max-age=60")
-                .unwrap()
-                .max_age
-                .unwrap(),
-            Duration::new(60, 0)
-        );
+    /// Returns whether the directive with the given name (e.g. `"max-age"`) is set. Names are
+    /// matched case-insensitively, and unknown names return false.
+    pub fn has_directive(&self, name: &str) -> bool {
+        let directives = [
+            ("public", self.cachability == Some(Cachability::Public)),
+            ("private", self.cachability == Some(Cachability::Private)),
+            ("no-cache", self.cachability == Some(Cachability::NoCache)),
+            (
+                "only-if-cached",
+                self.cachability == Some(Cachability::OnlyIfCached),
+            ),
+            ("max-age", self.max_age.is_some()),
+            ("s-maxage", self.s_max_age.is_some()),
+            ("max-stale", self.max_stale.is_some()),
+            ("min-fresh", self.min_fresh.is_some()),
+            ("must-revalidate", self.must_revalidate),
+            ("proxy-revalidate", self.proxy_revalidate),
+            ("immutable", self.immutable),
+            ("no-store", self.no_store),
+            ("no-transform", self.no_transform),
+            (
+                "stale-while-revalidate",
+                self.stale_while_revalidate.is_some(),
+            ),
+            ("stale-if-error", self.stale_if_error.is_some()),
+        ];
+        directives
+            .iter()
+            .any(|(directive, set)| *set && directive.eq_ignore_ascii_case(name))
     }
 
-    #[test]
-    fn test_from_value_multi() {
-        let test1 = &CacheControl::from_value("no-cache, no-store, must-revalidate").unwrap();
-        assert_eq!(test1.cachability, Some(Cachability::NoCache));
-        assert_eq!(test1.no_store, true);
-        assert_eq!(test1.must_revalidate, true);
-        assert_eq!(
-            *test1,
-            CacheControl {
-                cachability: Some(Cachability::NoCache),
-                max_age: None,
-                s_max_age: None,
-                max_stale: None,
-                min_fresh: None,
-                must_revalidate: true,
-                proxy_revalidate: false,
-                immutable: false,
-                no_store: true,
-                no_transform: false,
-                stale_while_revalidate: None,
-                stale_if_error: None
-            }
-        );
+    /// Bit for `must-revalidate` in `boolean_flags`.
+    pub const FLAG_MUST_REVALIDATE: u16 = 1 << 0;
+    /// Bit for `proxy-revalidate` in `boolean_flags`.
+    pub const FLAG_PROXY_REVALIDATE: u16 = 1 << 1;
+    /// Bit for `immutable` in `boolean_flags`.
+    pub const FLAG_IMMUTABLE: u16 = 1 << 2;
+    /// Bit for `no-store` in `boolean_flags`.
+    pub const FLAG_NO_STORE: u16 = 1 << 3;
+    /// Bit for `no-transform` in `boolean_flags`.
+    pub const FLAG_NO_TRANSFORM: u16 = 1 << 4;
+
+    /// Packs the boolean directives into a bitset.
+    ///
+    /// | Bit | Directive          |
+    /// |-----|--------------------|
+    /// | 0   | `must-revalidate`  |
+    /// | 1   | `proxy-revalidate` |
+    /// | 2   | `immutable`        |
+    /// | 3   | `no-store`         |
+    /// | 4   | `no-transform`     |
+    ///
+    /// The remaining bits are reserved and always zero.
+    pub fn boolean_flags(&self) -> u16 {
+        let mut flags = 0;
+        if self.must_revalidate {
+            flags |= CacheControl::FLAG_MUST_REVALIDATE;
+        }
+        if self.proxy_revalidate {
+            flags |= CacheControl::FLAG_PROXY_REVALIDATE;
+        }
+        if self.immutable {
+            flags |= CacheControl::FLAG_IMMUTABLE;
+        }
+        if self.no_store {
+            flags |= CacheControl::FLAG_NO_STORE;
+        }
+        if self.no_transform {
+            flags |= CacheControl::FLAG_NO_TRANSFORM;
+        }
+        flags
     }
 
-    #[test]
-    fn test_from_header() {
-        assert_eq!(
-            CacheControl::from_header("Cache-Control: ").unwrap(),
-            CacheControl::default()
-        );
-        assert_eq!(
-            CacheControl::from_header("Cache-Control: private")
-                .unwrap()
-                .cachability
-                .unwrap(),
-            Cachability::Private
-        );
-        assert_eq!(
-            CacheControl::from_header("Cache-Control: max-age=60")
-                .unwrap()
-                .max_age
-                .unwrap(),
-            Duration::new(60, 0)
-        );
-        assert_eq!(CacheControl::from_header("foo"), None);
-        assert_eq!(CacheControl::from_header("bar: max-age=60"), None);
+    /// Builds a CacheControl with only the boolean directives set, as packed by `boolean_flags`.
+    /// Reserved bits are ignored.
+    pub fn from_boolean_flags(flags: u16) -> CacheControl {
+        CacheControl {
+            must_revalidate: flags & CacheControl::FLAG_MUST_REVALIDATE != 0,
+            proxy_revalidate: flags & CacheControl::FLAG_PROXY_REVALIDATE != 0,
+            immutable: flags & CacheControl::FLAG_IMMUTABLE != 0,
+            no_store: flags & CacheControl::FLAG_NO_STORE != 0,
+            no_transform: flags & CacheControl::FLAG_NO_TRANSFORM != 0,
+            ..CacheControl::default()
+        }
     }
 
-    #[test]
-    fn test_from_header_multi() {
-        let test1 = &CacheControl::from_header("Cache-Control: public, max-age=600").unwrap();
-        assert_eq!(test1.cachability, Some(Cachability::Public));
-        assert_eq!(test1.max_age, Some(Duration::new(600, 0)));
-        assert_eq!(
-            *test1,
-            CacheControl {
-                cachability: Some(Cachability::Public),
-                max_age: Some(Duration::new(600, 0)),
-                s_max_age: None,
-                max_stale: None,
-                min_fresh: None,
-                must_revalidate: false,
-                proxy_revalidate: false,
-                immutable: false,
-                no_store: false,
-                no_transform: false,
-                stale_while_revalidate: None,
-                stale_if_error: None
+    /// Returns a plain-English summary of the policy, e.g.
+    /// `"Public, fresh for 600s, revalidate when stale"`.
+    ///
+    /// This is meant for humans (logs, debug dashboards) and its format is not stable. Use the
+    /// `Display` impl to get the header value.
+    pub fn describe(&self) -> String {
+        let mut phrases: Vec<String> = Vec::new();
+        match self.cachability {
+            Some(Cachability::Public) => phrases.push(String::from("Public")),
+            Some(Cachability::Private) => phrases.push(String::from("Private")),
+            Some(Cachability::NoCache) if self.no_cache_fields.is_empty() => {
+                phrases.push(String::from("Revalidate before every use"))
             }
-        );
+            Some(Cachability::NoCache) => phrases.push(format!(
+                "Revalidate before using {}",
+                self.no_cache_fields.join(", ")
+            )),
+            Some(Cachability::OnlyIfCached) => phrases.push(String::from("Only if cached")),
+            None => (),
+        }
+        if self.no_store {
+            phrases.push(String::from("never store"));
+        }
+        if let Some(max_age) = self.max_age {
+            phrases.push(format!("fresh for {}s", max_age.as_secs()));
+        }
+        if let Some(s_max_age) = self.s_max_age {
+            phrases.push(format!(
+                "fresh for {}s in shared caches",
+                s_max_age.as_secs()
+            ));
+        }
+        if let Some(max_stale) = self.max_stale {
+            phrases.push(format!("accept stale for {}s", max_stale.as_secs()));
+        }
+        if let Some(min_fresh) = self.min_fresh {
+            phrases.push(format!(
+                "require fresh for another {}s",
+                min_fresh.as_secs()
+            ));
+        }
+        if self.must_revalidate {
+            phrases.push(String::from("revalidate when stale"));
+        }
+        if self.proxy_revalidate {
+            phrases.push(String::from("shared caches revalidate when stale"));
+        }
+        if self.immutable {
+            phrases.push(String::from("unchanging while fresh"));
+        }
+        if self.no_transform {
+            phrases.push(String::from("do not transform"));
+        }
+        if let Some(stale_while_revalidate) = self.stale_while_revalidate {
+            phrases.push(format!(
+                "serve stale for {}s while revalidating",
+                stale_while_revalidate.as_secs()
+            ));
+        }
+        if let Some(stale_if_error) = self.stale_if_error {
+            phrases.push(format!(
+                "serve stale for {}s on error",
+                stale_if_error.as_secs()
+            ));
+        }
+
+        if phrases.is_empty() {
+            return String::from("No caching directives");
+        }
+        let mut description = phrases.join(", ");
+        description[..1].make_ascii_uppercase();
+        description
     }
 
-    #[test]
-    fn test_stale_while_revalidate() {
-        let test1 =
-            &CacheControl::from_header("Cache-Control: public, stale-while-revalidate=60").unwrap();
-        assert_eq!(
-            *test1,
-            CacheControl {
-                cachability: Some(Cachability::Public),
-                max_age: None,
-                s_max_age: None,
-                max_stale: None,
-                min_fresh: None,
-                must_revalidate: false,
-                proxy_revalidate: false,
-                immutable: false,
-                no_store: false,
-                no_transform: false,
-                stale_while_revalidate: Some(Duration::new(60, 0)),
-                stale_if_error: None
+    /// Returns whether swapping this policy for `other` would leave the caching behavior of the
+    /// given kind of cache unchanged.
+    ///
+    /// Both roles compare the cachability (including `no-cache` field names), `no-store`,
+    /// `immutable`, `no-transform`, `stale-while-revalidate` and `stale-if-error`. On top of that:
+    ///
+    /// * `CacheRole::Shared` compares the freshness lifetime of `s-maxage`, falling back to
+    ///   `max-age`, and whether the response must be revalidated once stale (`must-revalidate`,
+    ///   `proxy-revalidate` or `s-maxage`).
+    /// * `CacheRole::Private` compares `max-age` and `must-revalidate`, ignoring `s-maxage` and
+    ///   `proxy-revalidate`.
+    ///
+    /// Request-only directives (`max-stale`, `min-fresh`) are never compared.
+    pub fn behaves_same_for(&self, other: &CacheControl, role: CacheRole) -> bool {
+        let common = self.cachability == other.cachability
+            && same_fields(&self.no_cache_fields, &other.no_cache_fields)
+            && self.no_store == other.no_store
+            && self.immutable == other.immutable
+            && self.no_transform == other.no_transform
+            && self.stale_while_revalidate == other.stale_while_revalidate
+            && self.stale_if_error == other.stale_if_error;
+        let role_specific = match role {
+            CacheRole::Shared => {
+                self.s_max_age.or(self.max_age) == other.s_max_age.or(other.max_age)
+                    && self.forbids_stale(true) == other.forbids_stale(true)
             }
-        );
+            CacheRole::Private => {
+                self.max_age == other.max_age && self.must_revalidate == other.must_revalidate
+            }
+        };
+        common && role_specific
+    }
 
-        let test2 = &CacheControl::from_header("Cache-Control: public, stale-while-revalidate");
-        assert!(test2.is_none());
+    /// Returns the value of the HTTP/1.0 `Pragma` header matching this policy, i.e. `"no-cache"`
+    /// when `no-cache` is set, so it can be sent alongside Cache-Control for old caches.
+    pub fn pragma_equivalent(&self) -> Option<&'static str> {
+        if self.cachability == Some(Cachability::NoCache) {
+            Some("no-cache")
+        } else {
+            None
+        }
+    }
 
-        let test3 = &CacheControl::from_header("Cache-Control: public, stale-while-revalidate=abc");
-        assert!(test3.is_none());
+    /// Returns a normalized form of the policy for use in cache keys. Directives are sorted and
+    /// separated by a bare comma, and `no-cache` field names are lowercased and sorted, so
+    /// policies that are semantically equal produce the same fragment.
+    pub fn cache_key_fragment(&self) -> String {
+        let mut normalized = self.clone();
+        for field in normalized.no_cache_fields.iter_mut() {
+            field.make_ascii_lowercase();
+        }
+        normalized.no_cache_fields.sort();
+        normalized.no_cache_fields.dedup();
+        let mut directives = normalized.directives();
+        directives.sort();
+        directives.join(",")
     }
 
-    #[test]
-    fn test_stale_if_error() {
-        let test1 = &CacheControl::from_header("Cache-Control: public, stale-if-error=60").unwrap();
-        assert_eq!(
-            *test1,
+    /// Returns every set directive formatted as it appears in the header value.
+    fn directives(&self) -> Vec<String> {
+        let mut directives = Vec::new();
+        let _ = self.each_directive(|directive| {
+            directives.push(directive.to_string());
+            Ok(())
+        });
+        directives
+    }
+
+    /// Calls `write` with every set directive formatted as it appears in the header value, without
+    /// allocating.
+    fn each_directive(
+        &self,
+        mut write: impl FnMut(fmt::Arguments<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        // A bare number right after a delta-seconds directive reads as a thousands separator, as
+        // in `max-age=1, 000`, so the extensions go first when they start with one.
+        let numeric_first = self
+            .extensions
+            .first()
+            .is_some_and(|(name, value)| value.is_none() && is_digits(name));
+        if numeric_first {
+            self.each_extension(&mut write)?;
+        }
+        if let Some(cachability) = &self.cachability {
+            if *cachability == Cachability::NoCache && !self.no_cache_fields.is_empty() {
+                write(format_args!(
+                    "no-cache=\"{}\"",
+                    QuotedFields(&self.no_cache_fields)
+                ))?;
+            } else {
+                write(format_args!("{}", cachability.directive()))?;
+            }
+        }
+        let seconds = [
+            ("max-age", self.max_age),
+            ("s-maxage", self.s_max_age),
+            ("max-stale", self.max_stale),
+            ("min-fresh", self.min_fresh),
+        ];
+        for (directive, value) in seconds.iter() {
+            if let Some(value) = value {
+                write(format_args!("{}={}", directive, value.as_secs()))?;
+            }
+        }
+        let flags = [
+            ("must-revalidate", self.must_revalidate),
+            ("proxy-revalidate", self.proxy_revalidate),
+            ("immutable", self.immutable),
+            ("no-store", self.no_store),
+            ("no-transform", self.no_transform),
+        ];
+        for (directive, set) in flags.iter() {
+            if *set {
+                write(format_args!("{}", directive))?;
+            }
+        }
+
+        // RFC 5861 https://tools.ietf.org/html/rfc5861
+        let seconds = [
+            ("stale-while-revalidate", self.stale_while_revalidate),
+            ("stale-if-error", self.stale_if_error),
+        ];
+        for (directive, value) in seconds.iter() {
+            if let Some(value) = value {
+                write(format_args!("{}={}", directive, value.as_secs()))?;
+            }
+        }
+
+        if !numeric_first {
+            self.each_extension(&mut write)?;
+        }
+        Ok(())
+    }
+
+    /// Calls `write` with every extension formatted as it appears in the header value.
+    fn each_extension(
+        &self,
+        mut write: impl FnMut(fmt::Arguments<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        for (name, value) in self.extensions.iter() {
+            match value {
+                Some(value) => write(format_args!("{}={}", name, value))?,
+                None => write(format_args!("{}", name))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the cachability directive, if any.
+    pub fn cachability(&self) -> Option<&Cachability> {
+        self.cachability.as_ref()
+    }
+
+    /// Returns the value of `max-age`.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Returns the value of `s-maxage`.
+    pub fn s_max_age(&self) -> Option<Duration> {
+        self.s_max_age
+    }
+
+    /// Returns the value of `max-stale`.
+    pub fn max_stale(&self) -> Option<Duration> {
+        self.max_stale
+    }
+
+    /// Returns the value of `min-fresh`.
+    pub fn min_fresh(&self) -> Option<Duration> {
+        self.min_fresh
+    }
+
+    /// Returns the field names listed in `no-cache="..."`.
+    pub fn no_cache_fields(&self) -> &[String] {
+        &self.no_cache_fields
+    }
+
+    /// Returns whether `must-revalidate` is set.
+    pub fn is_must_revalidate(&self) -> bool {
+        self.must_revalidate
+    }
+
+    /// Returns whether `proxy-revalidate` is set.
+    pub fn is_proxy_revalidate(&self) -> bool {
+        self.proxy_revalidate
+    }
+
+    /// Returns whether `immutable` is set.
+    pub fn is_immutable(&self) -> bool {
+        self.immutable
+    }
+
+    /// Returns whether `no-store` is set.
+    pub fn is_no_store(&self) -> bool {
+        self.no_store
+    }
+
+    /// Returns whether `no-transform` is set.
+    pub fn is_no_transform(&self) -> bool {
+        self.no_transform
+    }
+
+    /// Returns the value of `stale-while-revalidate`.
+    pub fn stale_while_revalidate(&self) -> Option<Duration> {
+        self.stale_while_revalidate
+    }
+
+    /// Returns the value of `stale-if-error`.
+    pub fn stale_if_error(&self) -> Option<Duration> {
+        self.stale_if_error
+    }
+
+    /// Lowers `max-age` and `s-maxage` to `limit` when they exceed it. Smaller or missing values are
+    /// left untouched.
+    pub fn clamp_max_age(&mut self, limit: Duration) {
+        if let Some(max_age) = self.max_age {
+            self.max_age = Some(max_age.min(limit));
+        }
+        if let Some(s_max_age) = self.s_max_age {
+            self.s_max_age = Some(s_max_age.min(limit));
+        }
+    }
+
+    /// Returns a small JSON object of the set fields, keyed by field name, with durations in
+    /// seconds, e.g. `{"cachability":"public","max_age":60}`.
+    ///
+    /// This is only meant for debugging and its format is not stable.
+    pub fn to_debug_json(&self) -> String {
+        let mut members: Vec<String> = Vec::new();
+        if let Some(cachability) = &self.cachability {
+            members.push(format!(
+                "\"cachability\":{}",
+                json_string(cachability.directive())
+            ));
+        }
+        if !self.no_cache_fields.is_empty() {
+            let fields: Vec<String> = self
+                .no_cache_fields
+                .iter()
+                .map(|field| json_string(field))
+                .collect();
+            members.push(format!("\"no_cache_fields\":[{}]", fields.join(",")));
+        }
+        let seconds = [
+            ("max_age", self.max_age),
+            ("s_max_age", self.s_max_age),
+            ("max_stale", self.max_stale),
+            ("min_fresh", self.min_fresh),
+            ("stale_while_revalidate", self.stale_while_revalidate),
+            ("stale_if_error", self.stale_if_error),
+        ];
+        for (field, value) in seconds.iter() {
+            if let Some(value) = value {
+                members.push(format!("\"{}\":{}", field, value.as_secs()));
+            }
+        }
+        let flags = [
+            ("must_revalidate", self.must_revalidate),
+            ("proxy_revalidate", self.proxy_revalidate),
+            ("immutable", self.immutable),
+            ("no_store", self.no_store),
+            ("no_transform", self.no_transform),
+        ];
+        for (field, set) in flags.iter() {
+            if *set {
+                members.push(format!("\"{}\":true", field));
+            }
+        }
+        format!("{{{}}}", members.join(","))
+    }
+
+    /// Returns the freshness lifetime that applies to a shared or private cache: `s-maxage` for a
+    /// shared cache, falling back to `max-age`.
+    pub fn effective_max_age(&self, shared: bool) -> Option<Duration> {
+        if shared {
+            self.s_max_age.or(self.max_age)
+        } else {
+            self.max_age
+        }
+    }
+
+    /// Returns the time the response expires, given the value of its `Date` header, or `None`
+    /// when no max-age applies to the cache. Saturates at the latest representable time.
+    #[cfg(feature = "std")]
+    pub fn expires_at(&self, date: SystemTime, shared: bool) -> Option<SystemTime> {
+        let max_age = self.effective_max_age(shared)?;
+        Some(saturating_add(date, max_age))
+    }
+
+    /// Returns every pair of set directives that contradict each other:
+    ///
+    /// * `public` with `no-store`
+    /// * `private` with `s-maxage`
+    /// * `no-cache` with `immutable`
+    /// * `immutable` with `no-store`
+    /// * `no-store` with `stale-while-revalidate` or `stale-if-error`
+    pub fn conflicts(&self) -> Vec<ConflictError> {
+        let pairs = [
+            ("public", "no-store"),
+            ("private", "s-maxage"),
+            ("no-cache", "immutable"),
+            ("immutable", "no-store"),
+            ("no-store", "stale-while-revalidate"),
+            ("no-store", "stale-if-error"),
+        ];
+        pairs
+            .iter()
+            .filter(|(first, second)| self.has_directive(first) && self.has_directive(second))
+            .map(|&(first, second)| ConflictError { first, second })
+            .collect()
+    }
+
+    /// Fails with the first of `conflicts`, if any.
+    pub fn validate(&self) -> Result<(), ConflictError> {
+        match self.conflicts().into_iter().next() {
+            Some(conflict) => Err(conflict),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether a client holding a response of the given age should revalidate it before
+    /// use. `shared` selects the shared cache rules, where `s-maxage` and `proxy-revalidate` also
+    /// apply.
+    ///
+    /// | Policy                                      | Result |
+    /// |---------------------------------------------|--------|
+    /// | `no-cache`                                  | true   |
+    /// | max-age set and `age` below it (fresh)      | false  |
+    /// | max-age set and `age` at or above it        | true   |
+    /// | no max-age                                  | false  |
+    ///
+    /// Without a max-age, the response is not stale by any directive and heuristic freshness is
+    /// left to the caller. `must-revalidate` only applies once the response is stale, so it
+    /// doesn't change that.
+    pub fn client_should_revalidate(&self, age: Duration, shared: bool) -> bool {
+        if self.cachability == Some(Cachability::NoCache) {
+            return true;
+        }
+        self.effective_max_age(shared)
+            .is_some_and(|max_age| age >= max_age)
+    }
+
+    /// Returns whether the given field name is listed in `no-cache="..."`, ignoring case.
+    pub fn is_no_cache_field(&self, name: &str) -> bool {
+        self.no_cache_fields
+            .iter()
+            .any(|field| field.eq_ignore_ascii_case(name))
+    }
+
+    /// Parses directives from the start of `value`, returning the policy and the unconsumed rest
+    /// of the input. Cache-Control values don't nest, so parsing only stops at the end of the input
+    /// or at the first directive that fails to parse, where the returned tail begins. A value that
+    /// parses fully leaves an empty tail. For a delta-seconds value split by a thousands separator,
+    /// such as `max-age=1,000`, the tail begins at the directive.
+    pub fn parse_prefix(value: &str) -> (CacheControl, &str) {
+        let mut consumed = 0;
+        let mut previous_seconds = None;
+        for token in split_directives(value) {
+            let start = token.as_ptr() as usize - value.as_ptr() as usize;
+            let key = directive_name(token).trim();
+            if is_digits(token.trim()) {
+                if let Some(previous_start) = previous_seconds {
+                    consumed = previous_start;
+                    break;
+                }
+            }
+            if CacheControl::from_value(token).is_none() {
+                break;
+            }
+            previous_seconds = if takes_delta_seconds(key) {
+                Some(start)
+            } else {
+                None
+            };
+            consumed = (start + token.len() + 1).min(value.len());
+        }
+        let (prefix, tail) = value.split_at(consumed);
+        let prefix = prefix.strip_suffix(',').unwrap_or(prefix);
+        // Every consumed directive was checked, but never drop input if the prefix still fails.
+        match CacheControl::from_value(prefix) {
+            Some(cache_control) => (cache_control, tail),
+            None => (CacheControl::default(), value),
+        }
+    }
+
+    /// Returns the smallest effective max-age among `policies`, for a multi-tier cache that has to
+    /// honor the strictest tier. Policies without a max-age are ignored.
+    pub fn min_freshness(policies: &[CacheControl], shared: bool) -> Option<Duration> {
+        policies
+            .iter()
+            .filter_map(|policy| policy.effective_max_age(shared))
+            .min()
+    }
+
+    /// Returns the names of the freshness directives (`max-age`, `s-maxage`) whose value exceeds
+    /// `limit`, to warn about absurd TTLs without changing the policy.
+    pub fn exceeds_sane_limit(&self, limit: Duration) -> Vec<&'static str> {
+        let directives = [("max-age", self.max_age), ("s-maxage", self.s_max_age)];
+        directives
+            .iter()
+            .filter(|(_, value)| value.is_some_and(|value| value > limit))
+            .map(|&(directive, _)| directive)
+            .collect()
+    }
+
+    /// Starts building a policy programmatically.
+    /// # Example
+    /// ```
+    /// extern crate cache_control;
+    ///
+    /// use cache_control::CacheControl;
+    /// use std::time::Duration;
+    ///
+    /// let cache_control = CacheControl::builder()
+    ///     .public()
+    ///     .max_age(Duration::new(60, 0))
+    ///     .build();
+    /// assert_eq!(cache_control.to_string(), "public, max-age=60");
+    /// ```
+    pub fn builder() -> CacheControlBuilder {
+        CacheControlBuilder::default()
+    }
+
+    /// Returns a `CacheControlBuilder` seeded with this policy, for editing it fluently.
+    pub fn into_builder(self) -> CacheControlBuilder {
+        CacheControlBuilder {
+            cache_control: self,
+        }
+    }
+
+    /// Returns whether `max-age` is absent, zero or positive, to make handling of `max-age=0`
+    /// explicit.
+    pub fn max_age_state(&self) -> MaxAgeState {
+        match self.max_age {
+            None => MaxAgeState::Absent,
+            Some(max_age) if max_age == Duration::new(0, 0) => MaxAgeState::Zero,
+            Some(max_age) => MaxAgeState::Positive(max_age),
+        }
+    }
+
+    /// Returns the request Cache-Control value a client sends to force caches along the way to
+    /// revalidate with the origin, e.g. for a conditional GET. This is `no-cache`, which unlike
+    /// `max-age=0` does not let a cache serve its stored response without validating it.
+    pub fn revalidation_request_value() -> String {
+        CacheControl::builder().no_cache().build().to_string()
+    }
+
+    /// Returns whether this policy lets the given kind of cache keep the response fresh for longer
+    /// than `other`, or `None` when either has no effective max-age.
+    pub fn longer_lived_than(&self, other: &CacheControl, shared: bool) -> Option<bool> {
+        Some(self.effective_max_age(shared)? > other.effective_max_age(shared)?)
+    }
+
+    /// Returns the usual policy for fingerprinted static assets: `public, max-age=31536000,
+    /// immutable` (cacheable by anyone for a year, never revalidated while fresh).
+    pub fn static_asset() -> CacheControl {
+        CacheControl::builder()
+            .public()
+            .max_age(Duration::from_secs(31536000))
+            .immutable()
+            .build()
+    }
+
+    /// Returns the policy for responses carrying sensitive data: `private, no-store`. `no-store`
+    /// keeps every cache, including the browser's, from storing the response, and `private`
+    /// additionally tells shared caches that ignore `no-store` not to share it between users.
+    /// `no-cache` is often added as well, but it would be redundant with `no-store`, and
+    /// `cachability` can only hold one of `private` and `no-cache`.
+    pub fn sensitive() -> CacheControl {
+        CacheControl::builder().private().no_store().build()
+    }
+
+    /// Returns the policy to hand to a shared cache, or `None` when `private` or `no-store` forbid
+    /// shared caching. When `s-maxage` is set, it replaces `max-age` in the copy.
+    pub fn for_shared_cache(&self) -> Option<CacheControl> {
+        if self.cachability == Some(Cachability::Private) || self.no_store {
+            return None;
+        }
+        let mut ret = self.clone();
+        if let Some(s_max_age) = self.s_max_age {
+            ret.max_age = Some(s_max_age);
+        }
+        Some(ret)
+    }
+
+    /// Returns whether a stored copy of the response may later be refreshed by a
+    /// `304 Not Modified`. Only `no-store` prevents this: `no-cache` is not `no-store`, and a
+    /// `no-cache` response may be stored as long as it is revalidated, which is exactly what a
+    /// 304 does.
+    pub fn allows_304_reuse(&self) -> bool {
+        !self.no_store
+    }
+
+    /// Returns a heuristic freshness lifetime of `fraction` (commonly 0.1) of the time elapsed
+    /// between the `Last-Modified` and `Date` headers, as RFC 9111 allows when the response gives
+    /// no explicit freshness. Returns `None` when `max-age` or `s-maxage` is set, when
+    /// `last_modified` is after `date`, or when `fraction` is negative or not finite.
+    #[cfg(feature = "std")]
+    pub fn heuristic_freshness(
+        &self,
+        last_modified: SystemTime,
+        date: SystemTime,
+        fraction: f64,
+    ) -> Option<Duration> {
+        if self.max_age.is_some() || self.s_max_age.is_some() {
+            return None;
+        }
+        let elapsed = date.duration_since(last_modified).ok()?;
+        Duration::try_from_secs_f64(elapsed.as_secs_f64() * fraction).ok()
+    }
+
+    /// Parses all the Cache-Control entries of a `HeaderMap`, combined as if they were a single
+    /// comma-separated value. Returns `None` when there are none, or when one is not valid text.
+    #[cfg(feature = "http")]
+    pub fn from_header_map(headers: &http::HeaderMap) -> Option<CacheControl> {
+        let mut values = Vec::new();
+        for value in headers.get_all(http::header::CACHE_CONTROL) {
+            values.push(value.to_str().ok()?);
+        }
+        if values.is_empty() {
+            return None;
+        }
+        CacheControl::from_value(&values.join(", "))
+    }
+
+    /// Returns a copy without the request-only directives (`max-stale`, `min-fresh` and
+    /// `only-if-cached`), for proxies sanitizing the Cache-Control of a forwarded response.
+    pub fn response_directives_only(&self) -> CacheControl {
+        let mut ret = self.clone();
+        ret.max_stale = None;
+        ret.min_fresh = None;
+        if ret.cachability == Some(Cachability::OnlyIfCached) {
+            ret.cachability = None;
+        }
+        ret
+    }
+
+    /// Splits the policy into `(edge, browser)` policies for a CDN that sends different headers to
+    /// origin-facing caches and to clients:
+    ///
+    /// * The edge policy carries the shared freshness lifetime (`s-maxage`, falling back to
+    ///   `max-age`) as `s-maxage`, and has no `max-age`.
+    /// * The browser policy keeps `max-age`, and drops `s-maxage` and `proxy-revalidate`, which
+    ///   only apply to shared caches.
+    ///
+    /// All other directives are copied to both.
+    pub fn split_for_edge(&self) -> (CacheControl, CacheControl) {
+        let mut edge = self.clone();
+        edge.s_max_age = self.effective_max_age(true);
+        edge.max_age = None;
+
+        let mut browser = self.clone();
+        browser.s_max_age = None;
+        browser.proxy_revalidate = false;
+        (edge, browser)
+    }
+
+    /// Compares every field except the RFC 5861 extensions (`stale-while-revalidate` and
+    /// `stale-if-error`).
+    pub fn core_eq(&self, other: &CacheControl) -> bool {
+        CacheControl {
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            ..self.clone()
+        } == CacheControl {
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            ..other.clone()
+        }
+    }
+
+    /// Returns whether a shared cache (a proxy or CDN) may store the response: neither `private`
+    /// nor `no-store` is set.
+    pub fn is_shared_cacheable(&self) -> bool {
+        !self.no_store && self.cachability != Some(Cachability::Private)
+    }
+
+    /// Returns whether a private cache (a browser) may store the response, which is the case
+    /// unless `no-store` is set.
+    ///
+    /// Note that `private` does not prevent this: it keeps the response out of shared caches only,
+    /// and is meant for responses that may be cached by the user's own browser. `no-cache` does
+    /// not prevent it either, as the stored response just has to be revalidated before use.
+    pub fn is_private_cacheable(&self) -> bool {
+        !self.no_store
+    }
+
+    /// Returns whether any cache, shared or private, may store the response.
+    pub fn is_cacheable(&self) -> bool {
+        self.is_shared_cacheable() || self.is_private_cacheable()
+    }
+
+    /// Returns when a shared cache has to revalidate the response:
+    ///
+    /// * `no-cache`, or a shared freshness lifetime of zero, means `AlwaysRevalidate`.
+    /// * A shared freshness lifetime (`s-maxage`, falling back to `max-age`) means
+    ///   `RevalidateAfter` that long.
+    /// * Without one, the response is `NeverStale`. `must-revalidate` and `proxy-revalidate` only
+    ///   apply once it is stale, so they don't change that.
+    pub fn shared_revalidation_policy(&self) -> SharedRevalidation {
+        if self.cachability == Some(Cachability::NoCache) {
+            return SharedRevalidation::AlwaysRevalidate;
+        }
+        match self.effective_max_age(true) {
+            Some(max_age) if max_age == Duration::new(0, 0) => SharedRevalidation::AlwaysRevalidate,
+            Some(max_age) => SharedRevalidation::RevalidateAfter(max_age),
+            None => SharedRevalidation::NeverStale,
+        }
+    }
+
+    /// Formats the header value like `Display`, but with the directives sorted alphabetically by
+    /// name rather than grouped by meaning, for stable diffs.
+    pub fn to_string_sorted(&self) -> String {
+        let mut directives = self.directives();
+        directives.sort_by(|a, b| directive_name(a).cmp(directive_name(b)));
+        directives.join(", ")
+    }
+
+    /// Returns whether `immutable` has any effect. RFC 8246 only lets it extend a freshness
+    /// lifetime, so it is meaningless without a positive `max-age` or `s-maxage`.
+    pub fn is_immutable_meaningful(&self) -> bool {
+        let positive =
+            |lifetime: Option<Duration>| lifetime.is_some_and(|l| l > Duration::new(0, 0));
+        self.immutable && (positive(self.max_age) || positive(self.s_max_age))
+    }
+
+    /// Formats the header value like `Display`, but leaves out `immutable` when
+    /// `is_immutable_meaningful` is false. `Display` keeps it so that parsed values round-trip.
+    pub fn to_canonical_string(&self) -> String {
+        if self.immutable && !self.is_immutable_meaningful() {
+            let mut canonical = self.clone();
+            canonical.immutable = false;
+            return canonical.to_string();
+        }
+        self.to_string()
+    }
+
+    /// Returns the ages `(fresh_until, swr_until)` at which the response stops being fresh, and
+    /// then stops being servable while revalidating in the background (RFC 5861). The second is
+    /// extended by `grace_period`, so without `stale-while-revalidate`, or when `must-revalidate`
+    /// (or, for a shared cache, `proxy-revalidate` or `s-maxage`) forbids serving stale
+    /// responses, both are the effective max-age. Returns `None` when no max-age applies.
+    pub fn swr_window(&self, shared: bool) -> Option<(Duration, Duration)> {
+        let fresh_until = self.effective_max_age(shared)?;
+        let swr_until = fresh_until.saturating_add(self.grace_period(shared));
+        Some((fresh_until, swr_until))
+    }
+
+    /// Returns `max-age` as a `time::Duration`, saturating at `time::Duration::MAX`.
+    #[cfg(feature = "time")]
+    pub fn max_age_time(&self) -> Option<time::Duration> {
+        self.max_age
+            .map(|max_age| time::Duration::try_from(max_age).unwrap_or(time::Duration::MAX))
+    }
+
+    /// Returns the policy a cache effectively applies to this response when serving `request`:
+    ///
+    /// * The request's `max-age` caps `max-age` and `s-maxage`, and becomes the `max-age` of a
+    ///   response that had none.
+    /// * The request's `min-fresh` is then subtracted from both, since the client wants the
+    ///   response to stay fresh for that much longer.
+    /// * The request's `no-cache` turns the cachability into a bare `no-cache`.
+    ///
+    /// Other request directives leave the policy unchanged.
+    pub fn intersect_request(&self, request: &RequestCacheControl) -> CacheControl {
+        let mut ret = self.clone();
+        if let Some(limit) = request.max_age {
+            ret.max_age = Some(ret.max_age.map_or(limit, |max_age| max_age.min(limit)));
+            ret.s_max_age = ret.s_max_age.map(|s_max_age| s_max_age.min(limit));
+        }
+        if let Some(min_fresh) = request.min_fresh {
+            ret.max_age = ret.max_age.map(|max_age| max_age.saturating_sub(min_fresh));
+            ret.s_max_age = ret
+                .s_max_age
+                .map(|s_max_age| s_max_age.saturating_sub(min_fresh));
+        }
+        if request.no_cache {
+            ret.cachability = Some(Cachability::NoCache);
+            ret.no_cache_fields = Vec::new();
+        }
+        ret
+    }
+
+    /// Returns whether a cache may serve a stored response of the given age without contacting the
+    /// origin: it has to be fresh by its effective max-age, and neither `no-cache` nor `no-store`
+    /// may be set. Responses without a max-age are not fresh by any directive, so this is false
+    /// for them.
+    ///
+    /// Once stale, the answer is false. `must-revalidate` only makes that strict, forbidding the
+    /// exceptions (such as being disconnected from the origin) that would otherwise allow a stale
+    /// response to be served. `only-if-cached` doesn't change the answer either: it tells the cache
+    /// to reply with a 504 instead of contacting the origin when this is false.
+    pub fn can_serve_from_cache(&self, age: Duration, shared: bool) -> bool {
+        if self.no_store || self.cachability == Some(Cachability::NoCache) {
+            return false;
+        }
+        self.effective_max_age(shared)
+            .is_some_and(|max_age| age < max_age)
+    }
+
+    /// Builds a policy from typed values without parsing a header. `flags` names the boolean
+    /// directives to set: `must-revalidate`, `proxy-revalidate`, `immutable`, `no-store` and
+    /// `no-transform`. Other names are ignored.
+    pub fn from_typed(
+        cachability: Option<Cachability>,
+        max_age: Option<Duration>,
+        flags: &[&str],
+    ) -> CacheControl {
+        let mut ret = CacheControl {
+            cachability,
+            max_age,
+            ..CacheControl::default()
+        };
+        for flag in flags {
+            match *flag {
+                "must-revalidate" => ret.must_revalidate = true,
+                "proxy-revalidate" => ret.proxy_revalidate = true,
+                "immutable" => ret.immutable = true,
+                "no-store" => ret.no_store = true,
+                "no-transform" => ret.no_transform = true,
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    /// Returns whether the response may not be served stale: `must-revalidate` forbids it, and so
+    /// do `proxy-revalidate` and `s-maxage` in a shared cache (RFC 9111 section 5.2.2.10).
+    fn forbids_stale(&self, shared: bool) -> bool {
+        self.must_revalidate || (shared && (self.proxy_revalidate || self.s_max_age.is_some()))
+    }
+
+    /// Returns how long past its freshness a response may be served while it is revalidated:
+    /// `stale-while-revalidate`, unless `must-revalidate` (or, for a shared cache,
+    /// `proxy-revalidate` or `s-maxage`) forbids serving it stale. Zero otherwise.
+    pub fn grace_period(&self, shared: bool) -> Duration {
+        if self.forbids_stale(shared) {
+            return Duration::new(0, 0);
+        }
+        self.stale_while_revalidate.unwrap_or(Duration::new(0, 0))
+    }
+
+    /// Appends the value of the Cache-Control header, as formatted by `Display`, to `buf`. The
+    /// directives are written straight into it, so nothing else is allocated.
+    pub fn write_to(&self, buf: &mut Vec<u8>) {
+        // Writing to a `Vec` can't fail.
+        let _ = fmt::write(&mut ByteWriter(buf), format_args!("{}", self));
+    }
+
+    /// Returns whether a worker may proactively refresh the response in the background, i.e.
+    /// whether `stale-while-revalidate` is set and `no-store` is not.
+    pub fn allows_background_refresh(&self) -> bool {
+        self.stale_while_revalidate.is_some() && !self.no_store
+    }
+
+    /// Returns whether the response may be stored but must be revalidated before every reuse,
+    /// which is what `no-cache` means. `no-store`, often confused with it, forbids storing the
+    /// response at all, so this returns `false` when it is set.
+    /// # Example
+    /// ```
+    /// extern crate cache_control;
+    ///
+    /// use cache_control::CacheControl;
+    ///
+    /// assert!(CacheControl::from_value("no-cache").unwrap().store_but_revalidate());
+    /// assert!(!CacheControl::from_value("no-store").unwrap().store_but_revalidate());
+    /// ```
+    pub fn store_but_revalidate(&self) -> bool {
+        self.cachability == Some(Cachability::NoCache) && !self.no_store
+    }
+
+    /// Parses a percent-encoded value of the Cache-Control header, as sent by some transport
+    /// layers, like `from_value`. Returns `None` if the encoding is malformed or doesn't decode to
+    /// UTF-8.
+    #[cfg(feature = "percent-encoding")]
+    pub fn from_percent_encoded(value: &str) -> Option<CacheControl> {
+        CacheControl::from_value(&percent_decode(value)?)
+    }
+
+    /// Returns the set directives that were introduced after the original HTTP/1.1 specification
+    /// (RFC 2068), which caches from the HTTP/1.0 era implement at most: `s-maxage` (RFC 2616),
+    /// `stale-while-revalidate` and `stale-if-error` (RFC 5861), and `immutable` (RFC 8246). Old
+    /// caches ignore these, so the policy degrades to the remaining directives.
+    pub fn http_1_0_ignored_directives(&self) -> Vec<&'static str> {
+        let directives = [
+            ("s-maxage", self.s_max_age.is_some()),
+            ("immutable", self.immutable),
+            (
+                "stale-while-revalidate",
+                self.stale_while_revalidate.is_some(),
+            ),
+            ("stale-if-error", self.stale_if_error.is_some()),
+        ];
+        directives
+            .iter()
+            .filter(|(_, set)| *set)
+            .map(|(directive, _)| *directive)
+            .collect()
+    }
+
+    /// Checks the value of the Cache-Control header, reporting every problem instead of stopping
+    /// at the first: unknown and duplicate directives, non-canonical delta-seconds with leading
+    /// zeros (such as `max-age=0060`) and `conflicts` are warnings, and malformed values of known
+    /// directives are errors. Duplicates and conflicts point at the second directive involved.
+    ///
+    /// The value is parsed as a whole like `parse` in strict mode does. Whenever that fails, the
+    /// directive at fault is reported and the rest of the value is parsed again.
+    pub fn lint(value: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut directives: Vec<(&str, Range<usize>)> = split_directives(value)
+            .map(|token| {
+                let directive = token.trim();
+                let start = directive.as_ptr() as usize - value.as_ptr() as usize;
+                (directive, start..start + directive.len())
+            })
+            .collect();
+
+        let cache_control = loop {
+            let tokens = directives.iter().map(|(directive, _)| *directive);
+            let error = match CacheControl::parse_tokens(tokens, ParseMode::Strict, None)
+                .and_then(|report| CacheControl::check_report(report, ParseMode::Strict))
+            {
+                Ok(report) => break Some(report.cache_control),
+                Err(error) => error,
+            };
+            let (severity, name) = match &error {
+                ParseError::UnknownDirective(name) | ParseError::DuplicateDirective(name) => {
+                    (Severity::Warning, name)
+                }
+                ParseError::InvalidValue(name) => (Severity::Error, name),
+            };
+            let continued = |i: usize| {
+                directives
+                    .get(i + 1)
+                    .is_some_and(|(next, _)| is_digits(next.trim()))
+            };
+            let mut named =
+                (0..directives.len()).filter(|&i| directive_name(directives[i].0).trim() == *name);
+            let at_fault = match error {
+                ParseError::UnknownDirective(_) => named.next(),
+                ParseError::DuplicateDirective(_) => named.nth(1),
+                // Either the directive is malformed by itself, or its value continues after a
+                // thousands separator, as in `max-age=1,000`.
+                ParseError::InvalidValue(_) => named.find(|&i| {
+                    CacheControl::parse(directives[i].0, ParseMode::Strict).is_err() || continued(i)
+                }),
+            };
+            let first = match at_fault {
+                Some(first) => first,
+                None => break None,
+            };
+            let mut last = first;
+            if let ParseError::InvalidValue(_) = error {
+                while continued(last) {
+                    last += 1;
+                }
+            }
+            diagnostics.push(Diagnostic {
+                severity,
+                message: error.to_string(),
+                range: directives[first].1.start..directives[last].1.end,
+            });
+            directives.drain(first..=last);
+        };
+
+        for (directive, range) in directives.iter() {
+            let seconds = directive
+                .split_once('=')
+                .map_or("", |(_, value)| value.trim());
+            if seconds.len() > 1 && seconds.starts_with('0') && is_digits(seconds) {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    message: format!("leading zeros in {}", directive_name(directive).trim()),
+                    range: range.clone(),
+                });
+            }
+        }
+        if let Some(cache_control) = cache_control {
+            for conflict in cache_control.conflicts() {
+                let range = directives
+                    .iter()
+                    .find(|(directive, _)| directive_name(directive).trim() == conflict.second)
+                    .map_or(0..value.len(), |(_, range)| range.clone());
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    message: conflict.to_string(),
+                    range,
+                });
+            }
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+        diagnostics
+    }
+
+    /// Returns what a cache may do with a stored response of the given age. `had_error` tells
+    /// whether contacting the origin failed, which lets `stale-if-error` apply.
+    ///
+    /// `no-store`, or `private` in a shared cache, forbid reuse. A fresh response is served as is,
+    /// unless it has `no-cache`. Once stale, `no-cache`, `must-revalidate` and, in a shared cache,
+    /// `proxy-revalidate` or `s-maxage` require revalidation; otherwise the `stale-if-error` and
+    /// `stale-while-revalidate` windows are checked, in that order. Staleness is measured from the
+    /// effective max-age, or from zero without one.
+    pub fn reuse_decision(&self, age: Duration, shared: bool, had_error: bool) -> ReuseDecision {
+        if self.no_store || (shared && self.cachability == Some(Cachability::Private)) {
+            return ReuseDecision::MustNotReuse;
+        }
+        if self.can_serve_from_cache(age, shared) {
+            return ReuseDecision::Fresh;
+        }
+        if self.cachability == Some(Cachability::NoCache) || self.forbids_stale(shared) {
+            return ReuseDecision::StaleRevalidate;
+        }
+        let max_age = self
+            .effective_max_age(shared)
+            .unwrap_or(Duration::new(0, 0));
+        let staleness = age.saturating_sub(max_age);
+        if had_error && self.stale_if_error.is_some_and(|sie| staleness < sie) {
+            ReuseDecision::ServeStaleOnError
+        } else if staleness < self.grace_period(shared) {
+            ReuseDecision::ServeStaleWhileRevalidate
+        } else {
+            ReuseDecision::StaleRevalidate
+        }
+    }
+
+    /// Returns whether the response is specific to a user, so caches should key it per user: it is
+    /// `private`, or `no-cache` lists `Set-Cookie` or `Authorization` among its fields.
+    pub fn is_keyed_per_user(&self) -> bool {
+        self.cachability == Some(Cachability::Private)
+            || (self.cachability == Some(Cachability::NoCache)
+                && (self.is_no_cache_field("Set-Cookie")
+                    || self.is_no_cache_field("Authorization")))
+    }
+
+    /// Returns whether a CDN may collapse concurrent requests for the response into a single
+    /// origin fetch: it is shared cacheable, and `no-cache` doesn't require each use to be
+    /// revalidated.
+    pub fn allows_request_collapsing(&self) -> bool {
+        self.is_shared_cacheable() && self.cachability != Some(Cachability::NoCache)
+    }
+
+    /// Returns the policy as a `curl` argument sending it as a header, e.g.
+    /// `-H 'Cache-Control: public, max-age=60'`. The header is single-quoted so the shell doesn't
+    /// expand `$` or backticks in it, and each `'` in the value is written as `'\''`.
+    pub fn to_curl_header_arg(&self) -> String {
+        format!(
+            "-H 'Cache-Control: {}'",
+            self.to_string().replace('\'', "'\\''")
+        )
+    }
+
+    /// Returns the known directives that differ from `self` to `other`, in the order they are
+    /// formatted by `Display`. Extensions are not compared.
+    pub fn diff(&self, other: &CacheControl) -> Vec<FieldChange> {
+        let names = [
+            "public",
+            "private",
+            "no-cache",
+            "only-if-cached",
+            "max-age",
+            "s-maxage",
+            "max-stale",
+            "min-fresh",
+            "must-revalidate",
+            "proxy-revalidate",
+            "immutable",
+            "no-store",
+            "no-transform",
+            "stale-while-revalidate",
+            "stale-if-error",
+        ];
+        let (old, new) = (self.directives(), other.directives());
+        let find = |directives: &[String], name: &str| {
+            directives
+                .iter()
+                .find(|directive| directive_name(directive) == name)
+                .cloned()
+        };
+        names
+            .iter()
+            .map(|&directive| FieldChange {
+                directive,
+                old: find(&old, directive),
+                new: find(&new, directive),
+            })
+            .filter(|change| change.old != change.new)
+            .collect()
+    }
+
+    /// Returns the freshness lifetime a browser applies, which is `max-age`. Browsers are private
+    /// caches and never honor `s-maxage`, which only applies to shared caches.
+    pub fn browser_max_age(&self) -> Option<Duration> {
+        self.effective_max_age(false)
+    }
+
+    /// Returns whether the policy sets no known directive, leaving caches free to apply heuristic
+    /// freshness. Unknown directives in `extensions` don't constrain caches and are not considered.
+    pub fn imposes_no_constraints(&self) -> bool {
+        *self
+            == CacheControl {
+                extensions: self.extensions.clone(),
+                ..CacheControl::default()
+            }
+    }
+
+    /// Returns whether shared and private caches would treat the response differently: `s-maxage`
+    /// and `max-age` are both set to different lifetimes, or `private` keeps it out of shared
+    /// caches entirely.
+    pub fn caches_disagree(&self) -> bool {
+        let lifetimes_differ = match (self.s_max_age, self.max_age) {
+            (Some(s_max_age), Some(max_age)) => s_max_age != max_age,
+            _ => false,
+        };
+        lifetimes_differ || self.cachability == Some(Cachability::Private)
+    }
+
+    /// Returns how long the response has been stale at the given age: the time past its effective
+    /// max-age, or zero while it is fresh. A response without a max-age is never stale by any
+    /// directive, so this is zero for it.
+    pub fn staleness(&self, age: Duration, shared: bool) -> Duration {
+        match self.effective_max_age(shared) {
+            Some(max_age) => age.saturating_sub(max_age),
+            None => Duration::new(0, 0),
+        }
+    }
+
+    /// Returns the Cache-Control value to send with a 304 (Not Modified) response, which updates
+    /// the stored response's freshness:
+    ///
+    /// * `no-store` is dropped, since a 304 implies the response was stored.
+    /// * Request-only directives are dropped, as in `response_directives_only`.
+    /// * `no-transform` and unknown directives are dropped, since they don't affect freshness.
+    ///
+    /// Cachability, the max-ages, the revalidation directives, `immutable`,
+    /// `stale-while-revalidate` and `stale-if-error` are kept.
+    pub fn for_304_response(&self) -> String {
+        let mut ret = self.response_directives_only();
+        ret.no_store = false;
+        ret.no_transform = false;
+        ret.extensions.clear();
+        ret.to_string()
+    }
+
+    /// Returns the largest age at which the response may be served to a client that sent
+    /// `client_max_stale`: the effective max-age plus the accepted staleness, saturating. When
+    /// `must-revalidate` (or, for a shared cache, `proxy-revalidate` or `s-maxage`) forbids
+    /// serving it stale, this is just the effective max-age. Returns `None` when no max-age
+    /// applies.
+    pub fn extended_serveable_age(
+        &self,
+        client_max_stale: Option<Duration>,
+        shared: bool,
+    ) -> Option<Duration> {
+        let max_age = self.effective_max_age(shared)?;
+        if self.forbids_stale(shared) {
+            return Some(max_age);
+        }
+        Some(max_age.saturating_add(client_max_stale.unwrap_or(Duration::new(0, 0))))
+    }
+}
+
+/// Returns the first cachability directive (`public`, `private`, `no-cache` or `only-if-cached`)
+/// in the value of a Cache-Control header, without parsing the other directives.
+pub fn parse_cachability(value: &str) -> Option<Cachability> {
+    split_directives(value).find_map(|token| match token.split('=').next().unwrap().trim() {
+        "public" => Some(Cachability::Public),
+        "private" => Some(Cachability::Private),
+        "no-cache" => Some(Cachability::NoCache),
+        "only-if-cached" => Some(Cachability::OnlyIfCached),
+        _ => None,
+    })
+}
+
+/// Returns whether the value of a Cache-Control header contains the given directive, matching its
+/// name case-insensitively, without parsing the value or allocating.
+pub fn header_contains_directive(value: &str, directive: &str) -> bool {
+    split_directives(value).any(|token| {
+        token
+            .split('=')
+            .next()
+            .unwrap()
+            .trim()
+            .eq_ignore_ascii_case(directive)
+    })
+}
+
+/// Formats the value of the Cache-Control header (i.e. everything after "Cache-Control:").
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut separator = "";
+        self.each_directive(|directive| {
+            f.write_str(separator)?;
+            separator = ", ";
+            f.write_fmt(directive)
+        })
+    }
+}
+
+/// Writes formatted text into a byte buffer.
+struct ByteWriter<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for ByteWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Formats `no-cache` field names as the contents of a quoted-string, escaping quotes and
+/// backslashes.
+struct QuotedFields<'a>(&'a [String]);
+
+impl fmt::Display for QuotedFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, field) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            for c in field.chars() {
+                match c {
+                    '"' => f.write_str("\\\"")?,
+                    '\\' => f.write_str("\\\\")?,
+                    _ => write!(f, "{}", c)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the common `public, max-age=N` policy from the max-age alone.
+impl From<Duration> for CacheControl {
+    fn from(max_age: Duration) -> CacheControl {
+        CacheControl {
+            cachability: Some(Cachability::Public),
+            max_age: Some(max_age),
+            ..CacheControl::default()
+        }
+    }
+}
+
+/// The directives of a Cache-Control header sent in a request.
+///
+/// Requests use a different set of directives than responses, and unlike in `cachability`,
+/// `no-cache` and `only-if-cached` can be combined.
+/// # Example
+/// ```
+/// extern crate cache_control;
+///
+/// use cache_control::RequestCacheControl;
+///
+/// let request = RequestCacheControl::from_value("no-cache").unwrap();
+/// assert!(request.forces_origin_fetch());
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RequestCacheControl {
+    pub max_age: Option<Duration>,
+    /// A bare `max-stale`, accepting any staleness, is `Duration::MAX`.
+    pub max_stale: Option<Duration>,
+    pub min_fresh: Option<Duration>,
+    pub no_cache: bool,
+    pub no_store: bool,
+    pub no_transform: bool,
+    pub only_if_cached: bool,
+}
+
+impl RequestCacheControl {
+    /// Parses the value of the Cache-Control header of a request. Response-only and unknown
+    /// directives are ignored.
+    pub fn from_value(value: &str) -> Option<RequestCacheControl> {
+        let mut ret = RequestCacheControl::default();
+        for token in split_directives(value) {
+            let mut key_value = token.splitn(2, '=').map(|s| s.trim());
+            let key = key_value.next().unwrap();
+            let val = key_value.next();
+
+            match key {
+                "max-age" => ret.max_age = Some(require_seconds(val).ok()?),
+                "max-stale" => {
+                    ret.max_stale = match val {
+                        Some(_) => Some(require_seconds(val).ok()?),
+                        None => Some(Duration::MAX),
+                    }
+                }
+                "min-fresh" => ret.min_fresh = Some(require_seconds(val).ok()?),
+                "no-cache" => ret.no_cache = true,
+                "no-store" => ret.no_store = true,
+                "no-transform" => ret.no_transform = true,
+                "only-if-cached" => ret.only_if_cached = true,
+                _ => (),
+            };
+        }
+        Some(ret)
+    }
+
+    /// Returns whether the client asks for a response from the origin rather than a cache, as
+    /// browsers do on reload: the request has `no-cache` or `max-age=0`.
+    pub fn forces_origin_fetch(&self) -> bool {
+        self.no_cache || self.max_age == Some(Duration::new(0, 0))
+    }
+
+    /// Returns whether the request has `only-if-cached`, in which case a cache must answer with a
+    /// stored response or a 504 (Gateway Timeout) instead of contacting the origin.
+    pub fn requires_cached_only(&self) -> bool {
+        self.only_if_cached
+    }
+}
+
+/// Builds a `CacheControl`. Created by `CacheControl::builder`.
+#[derive(Clone, Debug, Default)]
+pub struct CacheControlBuilder {
+    cache_control: CacheControl,
+}
+
+impl CacheControlBuilder {
+    /// Sets `public`.
+    pub fn public(mut self) -> CacheControlBuilder {
+        self.cache_control.set_cachability(Cachability::Public);
+        self
+    }
+
+    /// Sets `private`.
+    pub fn private(mut self) -> CacheControlBuilder {
+        self.cache_control.set_cachability(Cachability::Private);
+        self
+    }
+
+    /// Sets a bare `no-cache`.
+    pub fn no_cache(mut self) -> CacheControlBuilder {
+        self.cache_control.set_cachability(Cachability::NoCache);
+        self
+    }
+
+    /// Sets `only-if-cached`.
+    pub fn only_if_cached(mut self) -> CacheControlBuilder {
+        self.cache_control
+            .set_cachability(Cachability::OnlyIfCached);
+        self
+    }
+
+    /// Sets the cachability directive, for a `Cachability` chosen at runtime.
+    pub fn cachability(mut self, cachability: Cachability) -> CacheControlBuilder {
+        self.cache_control.set_cachability(cachability);
+        self
+    }
+
+    /// Sets `max-age`.
+    pub fn max_age(mut self, max_age: Duration) -> CacheControlBuilder {
+        self.cache_control.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets `s-maxage`.
+    pub fn s_max_age(mut self, s_max_age: Duration) -> CacheControlBuilder {
+        self.cache_control.s_max_age = Some(s_max_age);
+        self
+    }
+
+    /// Sets `max-stale`.
+    pub fn max_stale(mut self, max_stale: Duration) -> CacheControlBuilder {
+        self.cache_control.max_stale = Some(max_stale);
+        self
+    }
+
+    /// Sets `min-fresh`.
+    pub fn min_fresh(mut self, min_fresh: Duration) -> CacheControlBuilder {
+        self.cache_control.min_fresh = Some(min_fresh);
+        self
+    }
+
+    /// Sets `must-revalidate`.
+    pub fn must_revalidate(mut self) -> CacheControlBuilder {
+        self.cache_control.must_revalidate = true;
+        self
+    }
+
+    /// Sets `proxy-revalidate`.
+    pub fn proxy_revalidate(mut self) -> CacheControlBuilder {
+        self.cache_control.proxy_revalidate = true;
+        self
+    }
+
+    /// Sets `immutable`.
+    pub fn immutable(mut self) -> CacheControlBuilder {
+        self.cache_control.immutable = true;
+        self
+    }
+
+    /// Sets `no-store`.
+    pub fn no_store(mut self) -> CacheControlBuilder {
+        self.cache_control.no_store = true;
+        self
+    }
+
+    /// Sets `no-transform`.
+    pub fn no_transform(mut self) -> CacheControlBuilder {
+        self.cache_control.no_transform = true;
+        self
+    }
+
+    /// Sets `stale-while-revalidate` (RFC 5861).
+    pub fn stale_while_revalidate(
+        mut self,
+        stale_while_revalidate: Duration,
+    ) -> CacheControlBuilder {
+        self.cache_control.stale_while_revalidate = Some(stale_while_revalidate);
+        self
+    }
+
+    /// Sets `stale-if-error` (RFC 5861).
+    pub fn stale_if_error(mut self, stale_if_error: Duration) -> CacheControlBuilder {
+        self.cache_control.stale_if_error = Some(stale_if_error);
+        self
+    }
+
+    pub fn build(self) -> CacheControl {
+        self.cache_control
+    }
+}
+
+/// Incrementally collects the bytes of a Cache-Control value, for HTTP parsers that receive
+/// headers in chunks. The value is parsed once all the bytes have been pushed.
+/// # Example
+/// ```
+/// extern crate cache_control;
+///
+/// use cache_control::CacheControlParser;
+/// use std::time::Duration;
+///
+/// let mut parser = CacheControlParser::new();
+/// parser.push_bytes(b"public, max-");
+/// parser.push_bytes(b"age=60");
+/// let cache_control = parser.finish().unwrap();
+/// assert_eq!(cache_control.max_age, Some(Duration::new(60, 0)));
+/// ```
+#[derive(Debug, Default)]
+pub struct CacheControlParser {
+    buffer: Vec<u8>,
+}
+
+impl CacheControlParser {
+    pub fn new() -> CacheControlParser {
+        CacheControlParser::default()
+    }
+
+    /// Appends the next chunk of the value.
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Parses the collected value. Returns `None` if it is not valid UTF-8 or fails to parse.
+    pub fn finish(self) -> Option<CacheControl> {
+        let value = core::str::from_utf8(&self.buffer).ok()?;
+        CacheControl::from_value(value)
+    }
+}
+
+/// Returns whether two lists of field names contain the same names, ignoring case and order.
+fn same_fields(a: &[String], b: &[String]) -> bool {
+    let contains = |fields: &[String], name: &String| {
+        fields.iter().any(|field| field.eq_ignore_ascii_case(name))
+    };
+    a.iter().all(|name| contains(b, name)) && b.iter().all(|name| contains(a, name))
+}
+
+/// Replaces each obs-fold (a CRLF followed by spaces or tabs) with a single space.
+fn unfold(value: &str) -> String {
+    let is_fold_whitespace = |c| c == ' ' || c == '\t';
+    let mut ret = String::new();
+    let mut rest = value;
+    while let Some(i) = rest.find("\r\n") {
+        let tail = &rest[i + 2..];
+        if tail.starts_with(is_fold_whitespace) {
+            ret.push_str(&rest[..i]);
+            ret.push(' ');
+            rest = tail.trim_start_matches(is_fold_whitespace);
+        } else {
+            ret.push_str(&rest[..i + 2]);
+            rest = tail;
+        }
+    }
+    ret.push_str(rest);
+    ret
+}
+
+/// Splits a header value into directives on commas that are not inside a quoted string.
+fn split_directives(value: &str) -> Directives<'_> {
+    Directives {
+        rest: Some(value),
+        separator: ',',
+        split_lines: false,
+    }
+}
+
+/// Like `split_directives`, but also splits on line breaks, for values that were joined with
+/// newlines instead of commas.
+fn split_directives_and_lines(value: &str) -> Directives<'_> {
+    Directives {
+        rest: Some(value),
+        separator: ',',
+        split_lines: true,
+    }
+}
+
+/// Like `split_directives`, but splits on semicolons, for the parameters of a structured field
+/// list member.
+#[cfg(feature = "cache-status")]
+fn split_params(value: &str) -> Directives<'_> {
+    Directives {
+        rest: Some(value),
+        separator: ';',
+        split_lines: false,
+    }
+}
+
+/// Iterator returned by `split_directives`.
+struct Directives<'a> {
+    rest: Option<&'a str>,
+    separator: char,
+    split_lines: bool,
+}
+
+impl<'a> Iterator for Directives<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let value = self.rest?;
+        let mut quoted = false;
+        let mut escaped = false;
+        for (i, c) in value.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if quoted && c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                quoted = !quoted;
+            } else if !quoted
+                && (c == self.separator || (self.split_lines && (c == '\r' || c == '\n')))
+            {
+                self.rest = Some(&value[i + 1..]);
+                return Some(&value[..i]);
+            }
+        }
+        self.rest = None;
+        Some(value)
+    }
+}
+
+/// Parses delta-seconds, a non-negative whole number of seconds made up only of ASCII digits.
+/// Values above `u64::MAX` seconds are rejected rather than wrapped.
+fn parse_delta_seconds(value: &str) -> Option<Duration> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().map(Duration::from_secs)
+}
+
+/// Parses the value of a directive that requires delta-seconds, failing when the value is missing
+/// or malformed. An empty value, as left by trimming `max-age=   `, counts as missing.
+fn require_seconds(val: Option<&str>) -> Result<Duration, ()> {
+    val.filter(|val| !val.is_empty())
+        .and_then(parse_delta_seconds)
+        .ok_or(())
+}
+
+/// Maps misspellings of directive names accepted in lenient mode to the real name.
+fn lenient_alias(key: &str) -> &str {
+    match key {
+        "max_age" | "maxage" => "max-age",
+        "s_maxage" | "s_max_age" | "smaxage" => "s-maxage",
+        "max_stale" => "max-stale",
+        "min_fresh" => "min-fresh",
+        "no_cache" | "nocache" => "no-cache",
+        "no_store" | "nostore" => "no-store",
+        "no_transform" => "no-transform",
+        "only_if_cached" => "only-if-cached",
+        "must_revalidate" => "must-revalidate",
+        "proxy_revalidate" => "proxy-revalidate",
+        "stale_while_revalidate" => "stale-while-revalidate",
+        "stale_if_error" => "stale-if-error",
+        _ => key,
+    }
+}
+
+/// Formats a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Adds a duration to a time, stopping at the latest time the platform can represent.
+#[cfg(feature = "std")]
+fn saturating_add(mut time: SystemTime, duration: Duration) -> SystemTime {
+    let mut remaining = duration;
+    let mut step = duration;
+    while remaining > Duration::new(0, 0) && step > Duration::new(0, 0) {
+        match time.checked_add(step) {
+            Some(sum) => {
+                time = sum;
+                remaining -= step;
+                step = step.min(remaining);
+            }
+            None => step /= 2,
+        }
+    }
+    time
+}
+
+/// Like `require_seconds`, but lowers values above `cap`, however large, to `cap`.
+fn require_capped_seconds(val: Option<&str>, cap: u64) -> Result<Duration, ()> {
+    let val = val.filter(|val| !val.is_empty()).ok_or(())?;
+    if !val.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    let seconds = val.parse().unwrap_or(u64::MAX);
+    Ok(Duration::from_secs(seconds.min(cap)))
+}
+
+/// Decodes `%XX` escapes, failing on a truncated or non-hex escape or on invalid UTF-8.
+#[cfg(feature = "percent-encoding")]
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = value.bytes();
+    let mut decoded = Vec::with_capacity(value.len());
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let high = char::from(bytes.next()?).to_digit(16)?;
+            let low = char::from(bytes.next()?).to_digit(16)?;
+            decoded.push((high * 16 + low) as u8);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Returns whether the value is made up only of ASCII digits, and is not empty.
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Returns whether the directive with the given name takes a delta-seconds value.
+fn takes_delta_seconds(key: &str) -> bool {
+    matches!(
+        key,
+        "max-age"
+            | "s-maxage"
+            | "max-stale"
+            | "min-fresh"
+            | "stale-while-revalidate"
+            | "stale-if-error"
+    )
+}
+
+/// Returns the name of a formatted directive, e.g. `max-age` for `max-age=60`.
+fn directive_name(directive: &str) -> &str {
+    directive.split('=').next().unwrap()
+}
+
+/// Removes one pair of surrounding quotes, without handling escapes.
+fn strip_quotes(value: &str) -> &str {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+/// Removes the surrounding quotes and backslash escapes from a quoted-string. Values that are not
+/// quoted are returned as-is.
+fn unquote(value: &str) -> String {
+    if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+        return String::from(value);
+    }
+    let mut ret = String::new();
+    let mut escaped = false;
+    for c in value[1..value.len() - 1].chars() {
+        if !escaped && c == '\\' {
+            escaped = true;
+        } else {
+            escaped = false;
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        header_contains_directive, json_string, parse_cachability, parse_delta_seconds,
+        require_capped_seconds, require_seconds, unfold, Cachability, CacheControl,
+        CacheControlParser, CacheRole, ConflictError, Diagnostic, FieldChange, MaxAgeState,
+        ParseError, ParseMode, RequestCacheControl, ReuseDecision, Severity, SharedRevalidation,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!(
+            CacheControl::from_value("").unwrap(),
+            CacheControl::default()
+        );
+        assert_eq!(
+            CacheControl::from_value("private")
+                .unwrap()
+                .cachability
+                .unwrap(),
+            Cachability::Private
+        );
+        assert_eq!(
+            CacheControl::from_value("max-age=60")
+                .unwrap()
+                .max_age
+                .unwrap(),
+            Duration::new(60, 0)
+        );
+    }
+
+    #[test]
+    fn test_from_value_blank() {
+        assert_eq!(
+            CacheControl::from_value("   ").unwrap(),
+            CacheControl::default()
+        );
+        assert_eq!(
+            CacheControl::from_value(",").unwrap(),
+            CacheControl::default()
+        );
+        assert_eq!(
+            CacheControl::from_value(",,,").unwrap(),
+            CacheControl::default()
+        );
+    }
+
+    #[test]
+    fn test_from_value_multi() {
+        let test1 = &CacheControl::from_value("no-cache, no-store, must-revalidate").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::NoCache));
+        assert!(test1.no_store);
+        assert!(test1.must_revalidate);
+        assert_eq!(
+            *test1,
+            CacheControl {
+                cachability: Some(Cachability::NoCache),
+                max_age: None,
+                s_max_age: None,
+                max_stale: None,
+                min_fresh: None,
+                no_cache_fields: Vec::new(),
+                must_revalidate: true,
+                proxy_revalidate: false,
+                immutable: false,
+                no_store: true,
+                no_transform: false,
+                stale_while_revalidate: None,
+                stale_if_error: None,
+                extensions: Vec::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_header() {
+        assert_eq!(
+            CacheControl::from_header("Cache-Control: ").unwrap(),
+            CacheControl::default()
+        );
+        assert_eq!(
+            CacheControl::from_header("Cache-Control: private")
+                .unwrap()
+                .cachability
+                .unwrap(),
+            Cachability::Private
+        );
+        assert_eq!(
+            CacheControl::from_header("Cache-Control: max-age=60")
+                .unwrap()
+                .max_age
+                .unwrap(),
+            Duration::new(60, 0)
+        );
+        assert_eq!(CacheControl::from_header("foo"), None);
+        assert_eq!(CacheControl::from_header("bar: max-age=60"), None);
+    }
+
+    #[test]
+    fn test_from_header_multi() {
+        let test1 = &CacheControl::from_header("Cache-Control: public, max-age=600").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(test1.max_age, Some(Duration::new(600, 0)));
+        assert_eq!(
+            *test1,
+            CacheControl {
+                cachability: Some(Cachability::Public),
+                max_age: Some(Duration::new(600, 0)),
+                s_max_age: None,
+                max_stale: None,
+                min_fresh: None,
+                no_cache_fields: Vec::new(),
+                must_revalidate: false,
+                proxy_revalidate: false,
+                immutable: false,
+                no_store: false,
+                no_transform: false,
+                stale_while_revalidate: None,
+                stale_if_error: None,
+                extensions: Vec::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_stale_while_revalidate() {
+        let test1 =
+            &CacheControl::from_header("Cache-Control: public, stale-while-revalidate=60").unwrap();
+        assert_eq!(
+            *test1,
+            CacheControl {
+                cachability: Some(Cachability::Public),
+                max_age: None,
+                s_max_age: None,
+                max_stale: None,
+                min_fresh: None,
+                no_cache_fields: Vec::new(),
+                must_revalidate: false,
+                proxy_revalidate: false,
+                immutable: false,
+                no_store: false,
+                no_transform: false,
+                stale_while_revalidate: Some(Duration::new(60, 0)),
+                stale_if_error: None,
+                extensions: Vec::new()
+            }
+        );
+
+        let test2 = &CacheControl::from_header("Cache-Control: public, stale-while-revalidate");
+        assert!(test2.is_none());
+
+        let test3 = &CacheControl::from_header("Cache-Control: public, stale-while-revalidate=abc");
+        assert!(test3.is_none());
+    }
+
+    #[test]
+    fn test_stale_if_error() {
+        let test1 = &CacheControl::from_header("Cache-Control: public, stale-if-error=60").unwrap();
+        assert_eq!(
+            *test1,
+            CacheControl {
+                cachability: Some(Cachability::Public),
+                max_age: None,
+                s_max_age: None,
+                max_stale: None,
+                min_fresh: None,
+                no_cache_fields: Vec::new(),
+                must_revalidate: false,
+                proxy_revalidate: false,
+                immutable: false,
+                no_store: false,
+                no_transform: false,
+                stale_while_revalidate: None,
+                stale_if_error: Some(Duration::new(60, 0)),
+                extensions: Vec::new()
+            }
+        );
+
+        let test2 = &CacheControl::from_header("Cache-Control: public, stale-if-error");
+        assert!(test2.is_none());
+
+        let test3 = &CacheControl::from_header("Cache-Control: public, stale-if-error=abc");
+        assert!(test3.is_none());
+    }
+
+    #[test]
+    fn test_boolean_flags() {
+        let test1 = &CacheControl::from_header(
+            "Cache-Control: must-revalidate, immutable, no-store, no-transform",
+        )
+        .unwrap();
+        let flags = test1.boolean_flags();
+        assert_eq!(
+            flags,
+            CacheControl::FLAG_MUST_REVALIDATE
+                | CacheControl::FLAG_IMMUTABLE
+                | CacheControl::FLAG_NO_STORE
+                | CacheControl::FLAG_NO_TRANSFORM
+        );
+        assert_eq!(CacheControl::from_boolean_flags(flags), *test1);
+
+        let test2 = CacheControl::from_boolean_flags(CacheControl::FLAG_PROXY_REVALIDATE);
+        assert!(test2.proxy_revalidate);
+        assert_eq!(test2.boolean_flags(), CacheControl::FLAG_PROXY_REVALIDATE);
+
+        assert_eq!(CacheControl::default().boolean_flags(), 0);
+        assert_eq!(CacheControl::from_boolean_flags(0), CacheControl::default());
+    }
+
+    #[test]
+    fn test_has_directive() {
+        let test1 = &CacheControl::from_value("max-age=60, immutable").unwrap();
+        assert!(test1.has_directive("max-age"));
+        assert!(test1.has_directive("Max-Age"));
+        assert!(test1.has_directive("immutable"));
+        assert!(!test1.has_directive("no-store"));
+        assert!(!test1.has_directive("nonexistent"));
+
+        let test2 = &CacheControl::from_value("private").unwrap();
+        assert!(test2.has_directive("private"));
+        assert!(!test2.has_directive("public"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(CacheControl::default().to_string(), "");
+        assert_eq!(
+            CacheControl::from_value("max-age=600, public, no-transform")
+                .unwrap()
+                .to_string(),
+            "public, max-age=600, no-transform"
+        );
+        assert_eq!(
+            CacheControl::from_value("no-store, stale-if-error=60")
+                .unwrap()
+                .to_string(),
+            "no-store, stale-if-error=60"
+        );
+    }
+
+    #[test]
+    fn test_no_cache_fields() {
+        let test1 = CacheControl::from_value("no-cache=\"Set-Cookie\"").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::NoCache));
+        assert_eq!(test1.no_cache_fields, vec!["Set-Cookie"]);
+        assert_eq!(test1.to_string(), "no-cache=\"Set-Cookie\"");
+        assert_eq!(CacheControl::from_value(&test1.to_string()).unwrap(), test1);
+
+        let test2 =
+            CacheControl::from_value("no-cache=\"Set-Cookie, Authorization\", max-age=60").unwrap();
+        assert_eq!(test2.no_cache_fields, vec!["Set-Cookie", "Authorization"]);
+        assert_eq!(test2.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(CacheControl::from_value(&test2.to_string()).unwrap(), test2);
+
+        let test3 = CacheControl {
+            cachability: Some(Cachability::NoCache),
+            no_cache_fields: vec![String::from("X-\"Odd\"")],
+            ..CacheControl::default()
+        };
+        assert_eq!(test3.to_string(), "no-cache=\"X-\\\"Odd\\\"\"");
+        assert_eq!(CacheControl::from_value(&test3.to_string()).unwrap(), test3);
+
+        let test4 = CacheControl::from_value("no-cache").unwrap();
+        assert!(test4.no_cache_fields.is_empty());
+        assert_eq!(test4.to_string(), "no-cache");
+
+        let test5 = CacheControl::from_value("no-cache=\"Set-Cookie\", public").unwrap();
+        assert_eq!(test5.cachability, Some(Cachability::Public));
+        assert!(test5.no_cache_fields.is_empty());
+        assert!(!test5.is_no_cache_field("set-cookie"));
+        assert_eq!(test5.to_string(), "public");
+        let test6 = CacheControl::from_value(&test5.to_string()).unwrap();
+        assert_eq!(test6, test5);
+        assert!(test6.behaves_same_for(&test5, CacheRole::Shared));
+        assert!(CacheControl::from_value("no-cache=\"Set-Cookie\", private")
+            .unwrap()
+            .no_cache_fields
+            .is_empty());
+    }
+
+    #[test]
+    fn test_value_whitespace() {
+        let test1 = &CacheControl::from_value("public, max-age =  60 ").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+
+        let test2 = &CacheControl::from_value("max-stale= 30 , min-fresh =10").unwrap();
+        assert_eq!(test2.max_stale, Some(Duration::new(30, 0)));
+        assert_eq!(test2.min_fresh, Some(Duration::new(10, 0)));
+    }
+
+    #[test]
+    fn test_parse_cachability() {
+        assert_eq!(
+            parse_cachability("private, max-age=60"),
+            Some(Cachability::Private)
+        );
+        assert_eq!(
+            parse_cachability("max-age=60, no-cache, public"),
+            Some(Cachability::NoCache)
+        );
+        assert_eq!(parse_cachability("max-age=60, no-store"), None);
+        assert_eq!(parse_cachability(""), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let test1 = CacheControl::from_value("public, max-age=600, must-revalidate").unwrap();
+        assert_eq!(
+            test1.describe(),
+            "Public, fresh for 600s, revalidate when stale"
+        );
+
+        let test2 = CacheControl::from_value("no-store, stale-if-error=60").unwrap();
+        let description = test2.describe();
+        assert!(description.contains("Never store"));
+        assert!(description.contains("serve stale for 60s on error"));
+
+        assert_eq!(CacheControl::default().describe(), "No caching directives");
+    }
+
+    #[test]
+    fn test_quoted_seconds() {
+        assert_eq!(CacheControl::from_value("max-age=\"60\""), None);
+        assert_eq!(
+            CacheControl::from_value_with_mode("max-age=\"60\"", ParseMode::Strict),
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("max-age=\"60\"", ParseMode::Lenient)
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("max-age=\"abc\"", ParseMode::Lenient),
+            None
+        );
+    }
+
+    #[test]
+    fn test_behaves_same_for() {
+        let test1 = CacheControl::from_value("public, max-age=60").unwrap();
+        let test2 = CacheControl {
+            s_max_age: Some(Duration::new(600, 0)),
+            ..CacheControl::from_value("public, max-age=600").unwrap()
+        };
+        let test3 = CacheControl {
+            s_max_age: Some(Duration::new(600, 0)),
+            ..CacheControl::from_value("public, max-age=60").unwrap()
+        };
+
+        assert!(!test1.behaves_same_for(&test2, CacheRole::Shared));
+        assert!(!test1.behaves_same_for(&test2, CacheRole::Private));
+        assert!(test2.behaves_same_for(&test3, CacheRole::Shared));
+        assert!(!test2.behaves_same_for(&test3, CacheRole::Private));
+        assert!(test1.behaves_same_for(&test3, CacheRole::Private));
+        assert!(!test1.behaves_same_for(&test3, CacheRole::Shared));
+
+        let test4 = CacheControl::from_value("public, max-age=60, proxy-revalidate").unwrap();
+        assert!(test1.behaves_same_for(&test4, CacheRole::Private));
+        assert!(!test1.behaves_same_for(&test4, CacheRole::Shared));
+
+        let test5 = CacheControl::from_value("private, max-age=60").unwrap();
+        assert!(!test1.behaves_same_for(&test5, CacheRole::Private));
+        assert!(!test1.behaves_same_for(&test5, CacheRole::Shared));
+    }
+
+    #[test]
+    fn test_pragma_equivalent() {
+        assert_eq!(
+            CacheControl::from_value("no-cache, no-store")
+                .unwrap()
+                .pragma_equivalent(),
+            Some("no-cache")
+        );
+        assert_eq!(CacheControl::default().pragma_equivalent(), None);
+        assert_eq!(
+            CacheControl::from_value("no-store")
+                .unwrap()
+                .pragma_equivalent(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_delta_seconds() {
+        assert_eq!(parse_delta_seconds("0"), Some(Duration::from_secs(0)));
+        assert_eq!(parse_delta_seconds("60"), Some(Duration::from_secs(60)));
+        assert_eq!(
+            parse_delta_seconds("18446744073709551615"),
+            Some(Duration::from_secs(u64::MAX))
+        );
+        assert_eq!(parse_delta_seconds("18446744073709551616"), None);
+        assert_eq!(parse_delta_seconds(""), None);
+        assert_eq!(parse_delta_seconds("-1"), None);
+        assert_eq!(parse_delta_seconds("+1"), None);
+        assert_eq!(parse_delta_seconds("1.5"), None);
+        assert_eq!(parse_delta_seconds("abc"), None);
+    }
+
+    #[test]
+    fn test_require_seconds() {
+        assert_eq!(require_seconds(Some("60")), Ok(Duration::new(60, 0)));
+        assert_eq!(require_seconds(Some("abc")), Err(()));
+        assert_eq!(require_seconds(None), Err(()));
+
+        for directive in &[
+            "max-age",
+            "s-maxage",
+            "max-stale",
+            "min-fresh",
+            "stale-while-revalidate",
+            "stale-if-error",
+        ] {
+            assert!(CacheControl::from_value(&format!("public, {}=60", directive)).is_some());
+            assert_eq!(
+                CacheControl::from_value(&format!("public, {}", directive)),
+                None
+            );
+            assert_eq!(
+                CacheControl::from_value(&format!("public, {}=abc", directive)),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_s_max_age() {
+        let test1 = CacheControl::from_value("public, max-age=60, s-maxage=600").unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(test1.s_max_age, Some(Duration::new(600, 0)));
+        assert!(CacheControl::from_value("s-maxage").is_none());
+    }
+
+    #[test]
+    fn test_cache_key_fragment() {
+        let test1 = CacheControl::from_value("public, max-age=60").unwrap();
+        let test2 = CacheControl::from_value("max-age=60 ,  public").unwrap();
+        assert_eq!(test1.cache_key_fragment(), "max-age=60,public");
+        assert_eq!(test1.cache_key_fragment(), test2.cache_key_fragment());
+
+        let test3 = CacheControl::from_value("no-cache=\"Set-Cookie, Authorization\"").unwrap();
+        let test4 = CacheControl::from_value("no-cache=\"authorization,set-cookie\"").unwrap();
+        assert_eq!(test3.cache_key_fragment(), test4.cache_key_fragment());
+        assert_ne!(test1.cache_key_fragment(), test3.cache_key_fragment());
+    }
+
+    #[test]
+    fn test_from_header_obs_fold() {
+        assert_eq!(unfold("public,\r\n\t max-age=60"), "public, max-age=60");
+        assert_eq!(unfold("public\r\nfoo"), "public\r\nfoo");
+
+        let test1 = &CacheControl::from_header("Cache-Control: public,\r\n  max-age=600").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(test1.max_age, Some(Duration::new(600, 0)));
+    }
+
+    #[test]
+    fn test_getters() {
+        let test1 = CacheControl::from_value(
+            "no-cache=\"Set-Cookie\", max-age=60, s-maxage=600, no-store, stale-if-error=30",
+        )
+        .unwrap();
+        assert_eq!(test1.cachability(), Some(&Cachability::NoCache));
+        assert_eq!(test1.no_cache_fields(), &[String::from("Set-Cookie")]);
+        assert_eq!(test1.max_age(), Some(Duration::new(60, 0)));
+        assert_eq!(test1.s_max_age(), Some(Duration::new(600, 0)));
+        assert_eq!(test1.max_stale(), None);
+        assert_eq!(test1.min_fresh(), None);
+        assert!(test1.is_no_store());
+        assert!(!test1.is_must_revalidate());
+        assert!(!test1.is_proxy_revalidate());
+        assert!(!test1.is_immutable());
+        assert!(!test1.is_no_transform());
+        assert_eq!(test1.stale_while_revalidate(), None);
+        assert_eq!(test1.stale_if_error(), Some(Duration::new(30, 0)));
+    }
+
+    #[test]
+    fn test_parser() {
+        let mut parser = CacheControlParser::new();
+        parser.push_bytes(b"max-age=");
+        parser.push_bytes(b"60, no-store");
+        let test1 = parser.finish().unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert!(test1.no_store);
+
+        assert_eq!(
+            CacheControlParser::new().finish().unwrap(),
+            CacheControl::default()
+        );
+
+        let mut parser = CacheControlParser::new();
+        parser.push_bytes(&[0xff, 0xfe]);
+        assert_eq!(parser.finish(), None);
+    }
+
+    #[test]
+    fn test_clamp_max_age() {
+        let day = Duration::new(86400, 0);
+
+        let mut test1 = CacheControl::from_value("max-age=31536000, s-maxage=172800").unwrap();
+        test1.clamp_max_age(day);
+        assert_eq!(test1.max_age, Some(day));
+        assert_eq!(test1.s_max_age, Some(day));
+
+        let mut test2 = CacheControl::from_value("max-age=60, s-maxage=600").unwrap();
+        test2.clamp_max_age(day);
+        assert_eq!(test2.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(test2.s_max_age, Some(Duration::new(600, 0)));
+
+        let mut test3 = CacheControl::from_value("public").unwrap();
+        test3.clamp_max_age(day);
+        assert_eq!(test3.max_age, None);
+        assert_eq!(test3.s_max_age, None);
+    }
+
+    #[test]
+    fn test_underscore_aliases() {
+        assert_eq!(
+            CacheControl::from_value_with_mode("max_age=60", ParseMode::Strict)
+                .unwrap()
+                .max_age,
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("max_age=60", ParseMode::Lenient)
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+        let test1 =
+            CacheControl::from_value_with_mode("no_store, s_maxage=600", ParseMode::Lenient)
+                .unwrap();
+        assert!(test1.no_store);
+        assert_eq!(test1.s_max_age, Some(Duration::new(600, 0)));
+    }
+
+    #[test]
+    fn test_to_debug_json() {
+        let test1 = CacheControl::from_value("public, max-age=60, no-store").unwrap();
+        let json = test1.to_debug_json();
+        assert!(json.contains("\"max_age\":60"));
+        assert_eq!(
+            json,
+            "{\"cachability\":\"public\",\"max_age\":60,\"no_store\":true}"
+        );
+
+        let test2 = CacheControl::from_value("no-cache=\"Set-Cookie\"").unwrap();
+        assert_eq!(
+            test2.to_debug_json(),
+            "{\"cachability\":\"no-cache\",\"no_cache_fields\":[\"Set-Cookie\"]}"
+        );
+        assert_eq!(CacheControl::default().to_debug_json(), "{}");
+        assert_eq!(json_string("a\"b\\\n"), "\"a\\\"b\\\\\\u000a\"");
+    }
+
+    #[test]
+    fn test_effective_max_age() {
+        let test1 = CacheControl::from_value("max-age=60, s-maxage=600").unwrap();
+        assert_eq!(test1.effective_max_age(true), Some(Duration::new(600, 0)));
+        assert_eq!(test1.effective_max_age(false), Some(Duration::new(60, 0)));
+
+        let test2 = CacheControl::from_value("max-age=60").unwrap();
+        assert_eq!(test2.effective_max_age(true), Some(Duration::new(60, 0)));
+        assert_eq!(CacheControl::default().effective_max_age(true), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_expires_at() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let date = UNIX_EPOCH + Duration::new(1_000_000, 0);
+        let test1 = CacheControl::from_value("max-age=60, s-maxage=600").unwrap();
+        assert_eq!(
+            test1.expires_at(date, true),
+            Some(date + Duration::new(600, 0))
+        );
+        assert_eq!(
+            test1.expires_at(date, false),
+            Some(date + Duration::new(60, 0))
+        );
+
+        let test2 = CacheControl::from_value("s-maxage=600").unwrap();
+        assert_eq!(test2.expires_at(date, false), None);
+        assert_eq!(CacheControl::default().expires_at(date, true), None);
+
+        let test3 = CacheControl::from_value("max-age=18446744073709551615").unwrap();
+        let far_future = test3.expires_at(SystemTime::now(), false).unwrap();
+        assert!(far_future > SystemTime::now() + Duration::new(31536000 * 1000, 0));
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let test1 = CacheControl::from_value("public, max-age=60, stale-if-error=60").unwrap();
+        assert!(test1.conflicts().is_empty());
+        assert_eq!(test1.validate(), Ok(()));
+
+        let test2 = CacheControl::from_value("public, no-store, stale-if-error=60").unwrap();
+        assert_eq!(
+            test2.conflicts(),
+            vec![
+                ConflictError {
+                    first: "public",
+                    second: "no-store"
+                },
+                ConflictError {
+                    first: "no-store",
+                    second: "stale-if-error"
+                },
+            ]
+        );
+        let error = test2.validate().unwrap_err();
+        assert_eq!(
+            error,
+            ConflictError {
+                first: "public",
+                second: "no-store"
+            }
+        );
+        assert_eq!(error.to_string(), "public with no-store");
+    }
+
+    #[test]
+    fn test_tab_whitespace() {
+        let test1 = CacheControl::from_value("\tmax-age=60\t,\tno-store\t").unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert!(test1.no_store);
+
+        let test2 = CacheControl::from_value("max-age\t=\t60,\tpublic").unwrap();
+        assert_eq!(test2.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(test2.cachability, Some(Cachability::Public));
+
+        let test3 = CacheControl::from_header("Cache-Control:\tpublic").unwrap();
+        assert_eq!(test3.cachability, Some(Cachability::Public));
+    }
+
+    #[test]
+    fn test_client_should_revalidate() {
+        let minute = Duration::new(60, 0);
+        let hour = Duration::new(3600, 0);
+
+        let test1 = CacheControl::from_value("no-cache, max-age=3600").unwrap();
+        assert!(test1.client_should_revalidate(minute, false));
+
+        let test2 = CacheControl::from_value("max-age=600").unwrap();
+        assert!(!test2.client_should_revalidate(minute, false));
+        assert!(test2.client_should_revalidate(hour, false));
+
+        let test3 = CacheControl::from_value("max-age=600, must-revalidate").unwrap();
+        assert!(!test3.client_should_revalidate(minute, false));
+        assert!(test3.client_should_revalidate(hour, false));
+
+        let test4 = CacheControl::from_value("must-revalidate").unwrap();
+        assert!(!test4.client_should_revalidate(minute, false));
+        assert!(!CacheControl::default().client_should_revalidate(hour, false));
+
+        let test5 = CacheControl::from_value("proxy-revalidate").unwrap();
+        assert!(!test5.client_should_revalidate(minute, true));
+        assert!(!test5.client_should_revalidate(minute, false));
+
+        let test6 = CacheControl::from_value("max-age=60, s-maxage=7200").unwrap();
+        assert!(!test6.client_should_revalidate(hour, true));
+        assert!(test6.client_should_revalidate(hour, false));
+    }
+
+    #[test]
+    fn test_no_cache_fields_casing() {
+        let test1 = CacheControl::from_value("no-cache=\"Set-Cookie\"").unwrap();
+        assert_eq!(test1.no_cache_fields, vec!["Set-Cookie"]);
+        assert!(test1.is_no_cache_field("set-cookie"));
+        assert!(test1.is_no_cache_field("SET-COOKIE"));
+        assert!(!test1.is_no_cache_field("Authorization"));
+
+        let test2 = CacheControl::from_value("no-cache=\"set-cookie\"").unwrap();
+        assert_ne!(test1, test2);
+        assert!(test1.behaves_same_for(&test2, CacheRole::Shared));
+        assert!(test1.behaves_same_for(&test2, CacheRole::Private));
+    }
+
+    #[test]
+    fn test_header_contains_directive() {
+        assert!(header_contains_directive(
+            "max-age=60, no-store",
+            "no-store"
+        ));
+        assert!(header_contains_directive(
+            "max-age=60, No-Store",
+            "no-store"
+        ));
+        assert!(header_contains_directive("max-age=60, no-store", "max-age"));
+        assert!(!header_contains_directive("max-age=60", "no-store"));
+        assert!(!header_contains_directive("no-store-extra", "no-store"));
+        assert!(!header_contains_directive(
+            "no-cache=\"a, no-store\"",
+            "no-store"
+        ));
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        let (test1, tail) = CacheControl::parse_prefix("public, max-age=60");
+        assert_eq!(
+            test1,
+            CacheControl::from_value("public, max-age=60").unwrap()
+        );
+        assert_eq!(tail, "");
+
+        let (test2, tail) = CacheControl::parse_prefix("no-store, max-age=abc, public");
+        assert!(test2.no_store);
+        assert_eq!(test2.cachability, None);
+        assert_eq!(tail, " max-age=abc, public");
+
+        let (test3, tail) = CacheControl::parse_prefix("");
+        assert_eq!(test3, CacheControl::default());
+        assert_eq!(tail, "");
+
+        let (test4, tail) = CacheControl::parse_prefix("max-age=1,000");
+        assert_eq!(test4, CacheControl::default());
+        assert_eq!(tail, "max-age=1,000");
+
+        let (test5, tail) = CacheControl::parse_prefix("max-age=60, s-maxage=1,000, public");
+        assert_eq!(test5, CacheControl::from_value("max-age=60").unwrap());
+        assert_eq!(tail, " s-maxage=1,000, public");
+
+        let (test6, tail) = CacheControl::parse_prefix("public, 000");
+        assert_eq!(test6, CacheControl::from_value("public, 000").unwrap());
+        assert_eq!(tail, "");
+    }
+
+    #[test]
+    fn test_min_freshness() {
+        let policies = [
+            CacheControl::from_value("max-age=600").unwrap(),
+            CacheControl::from_value("no-store").unwrap(),
+            CacheControl::from_value("max-age=300, s-maxage=900").unwrap(),
+            CacheControl::from_value("public").unwrap(),
+        ];
+        assert_eq!(
+            CacheControl::min_freshness(&policies, false),
+            Some(Duration::new(300, 0))
+        );
+        assert_eq!(
+            CacheControl::min_freshness(&policies, true),
+            Some(Duration::new(600, 0))
+        );
+        assert_eq!(CacheControl::min_freshness(&policies[1..2], true), None);
+        assert_eq!(CacheControl::min_freshness(&[], true), None);
+    }
+
+    #[test]
+    fn test_exceeds_sane_limit() {
+        let year = Duration::new(31536000, 0);
+
+        let test1 = CacheControl::from_value("max-age=31536000000, s-maxage=60").unwrap();
+        assert_eq!(test1.exceeds_sane_limit(year), vec!["max-age"]);
+
+        let test2 = CacheControl::from_value("max-age=31536000000, s-maxage=31536001").unwrap();
+        assert_eq!(test2.exceeds_sane_limit(year), vec!["max-age", "s-maxage"]);
+
+        let test3 = CacheControl::from_value("max-age=31536000").unwrap();
+        assert!(test3.exceeds_sane_limit(year).is_empty());
+    }
+
+    #[test]
+    fn test_stray_punctuation() {
+        assert_eq!(
+            CacheControl::from_value_with_mode(".max-age=60", ParseMode::Strict)
+                .unwrap()
+                .max_age,
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode(".max-age=60", ParseMode::Lenient)
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+        assert!(
+            CacheControl::from_value_with_mode("no-store;, public", ParseMode::Lenient)
+                .unwrap()
+                .no_store
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let test1 = CacheControl::builder()
+            .public()
+            .max_age(Duration::new(600, 0))
+            .stale_while_revalidate(Duration::new(60, 0))
+            .stale_if_error(Duration::new(86400, 0))
+            .build();
+        assert_eq!(test1.stale_while_revalidate, Some(Duration::new(60, 0)));
+        assert_eq!(test1.stale_if_error, Some(Duration::new(86400, 0)));
+        assert_eq!(
+            test1.to_string(),
+            "public, max-age=600, stale-while-revalidate=60, stale-if-error=86400"
+        );
+        assert_eq!(CacheControl::from_value(&test1.to_string()).unwrap(), test1);
+
+        let test2 = CacheControl::builder()
+            .no_cache()
+            .no_store()
+            .must_revalidate()
+            .build();
+        assert_eq!(
+            test2,
+            CacheControl::from_value("no-cache, no-store, must-revalidate").unwrap()
+        );
+        assert_eq!(CacheControl::builder().build(), CacheControl::default());
+    }
+
+    #[test]
+    fn test_max_age_state() {
+        assert_eq!(
+            CacheControl::from_value("public").unwrap().max_age_state(),
+            MaxAgeState::Absent
+        );
+        assert_eq!(
+            CacheControl::from_value("max-age=0")
+                .unwrap()
+                .max_age_state(),
+            MaxAgeState::Zero
+        );
+        assert_eq!(
+            CacheControl::from_value("max-age=60")
+                .unwrap()
+                .max_age_state(),
+            MaxAgeState::Positive(Duration::new(60, 0))
+        );
+    }
+
+    #[test]
+    fn test_revalidation_request_value() {
+        let value = CacheControl::revalidation_request_value();
+        assert_eq!(value, "no-cache");
+        let test1 = CacheControl::from_value(&value).unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::NoCache));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            CacheControl::parse("public, garbage-text-no-equals", ParseMode::Strict),
+            Err(ParseError::UnknownDirective(String::from(
+                "garbage-text-no-equals"
+            )))
+        );
+        assert_eq!(
+            CacheControl::parse("x-foo=1", ParseMode::Strict),
+            Err(ParseError::UnknownDirective(String::from("x-foo")))
+        );
+
+        let report = CacheControl::parse("garbage-text-no-equals", ParseMode::Lenient).unwrap();
+        assert_eq!(
+            report.cache_control,
+            CacheControl {
+                extensions: vec![(String::from("garbage-text-no-equals"), None)],
+                ..CacheControl::default()
+            }
+        );
+        assert_eq!(report.ignored, vec!["garbage-text-no-equals"]);
+
+        let report =
+            CacheControl::parse("public, x-foo = 1, max-age=60", ParseMode::Lenient).unwrap();
+        assert_eq!(
+            report.cache_control,
+            CacheControl {
+                extensions: vec![(String::from("x-foo"), Some(String::from("1")))],
+                ..CacheControl::from_value("public, max-age=60").unwrap()
+            }
+        );
+        assert_eq!(report.ignored, vec!["x-foo = 1"]);
+
+        let report = CacheControl::parse("public, max-age=60", ParseMode::Strict).unwrap();
+        assert!(report.ignored.is_empty());
+
+        assert_eq!(
+            CacheControl::parse("max-age=abc", ParseMode::Lenient),
+            Err(ParseError::InvalidValue(String::from("max-age")))
+        );
+        assert_eq!(
+            CacheControl::from_value("garbage-text-no-equals").unwrap(),
+            CacheControl {
+                extensions: vec![(String::from("garbage-text-no-equals"), None)],
+                ..CacheControl::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_longer_lived_than() {
+        let test1 = CacheControl::from_value("max-age=600").unwrap();
+        let test2 = CacheControl::from_value("max-age=60, s-maxage=3600").unwrap();
+        assert_eq!(test1.longer_lived_than(&test2, false), Some(true));
+        assert_eq!(test2.longer_lived_than(&test1, false), Some(false));
+        assert_eq!(test1.longer_lived_than(&test2, true), Some(false));
+        assert_eq!(test1.longer_lived_than(&test1, false), Some(false));
+
+        let test3 = CacheControl::from_value("no-store").unwrap();
+        assert_eq!(test1.longer_lived_than(&test3, false), None);
+        assert_eq!(test3.longer_lived_than(&test1, false), None);
+    }
+
+    #[test]
+    fn test_static_asset() {
+        let test1 = CacheControl::static_asset();
+        assert_eq!(test1.to_string(), "public, max-age=31536000, immutable");
+        assert_eq!(
+            CacheControl::from_value("public, max-age=31536000, immutable").unwrap(),
+            test1
+        );
+    }
+
+    #[test]
+    fn test_for_shared_cache() {
+        let test1 = CacheControl::from_value("private, max-age=60").unwrap();
+        assert_eq!(test1.for_shared_cache(), None);
+        let test2 = CacheControl::from_value("public, no-store").unwrap();
+        assert_eq!(test2.for_shared_cache(), None);
+
+        let test3 = CacheControl::from_value("public, max-age=60, s-maxage=600").unwrap();
+        let shared = test3.for_shared_cache().unwrap();
+        assert_eq!(shared.max_age, Some(Duration::new(600, 0)));
+        assert_eq!(shared.cachability, Some(Cachability::Public));
+
+        let test4 = CacheControl::from_value("public, max-age=60").unwrap();
+        assert_eq!(test4.for_shared_cache(), Some(test4.clone()));
+    }
+
+    #[test]
+    fn test_from_value_cow() {
+        use std::borrow::Cow;
+
+        let expected = CacheControl::from_value("public, max-age=60").unwrap();
+        assert_eq!(
+            CacheControl::from_value_cow(Cow::Borrowed("public, max-age=60")).unwrap(),
+            expected
+        );
+        assert_eq!(
+            CacheControl::from_value_cow(Cow::Owned(String::from("public, max-age=60"))).unwrap(),
+            expected
+        );
+        assert_eq!(
+            CacheControl::from_value_cow(String::from("public, max-age=60")).unwrap(),
+            expected
+        );
+        assert_eq!(CacheControl::from_value_cow("max-age=abc"), None);
+    }
+
+    #[test]
+    fn test_allows_304_reuse() {
+        assert!(!CacheControl::from_value("no-store")
+            .unwrap()
+            .allows_304_reuse());
+        assert!(CacheControl::from_value("no-cache")
+            .unwrap()
+            .allows_304_reuse());
+        assert!(CacheControl::from_value("max-age=60, immutable")
+            .unwrap()
+            .allows_304_reuse());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_heuristic_freshness() {
+        use std::time::UNIX_EPOCH;
+
+        let last_modified = UNIX_EPOCH + Duration::new(1_000_000, 0);
+        let date = last_modified + Duration::new(36000, 0);
+
+        let test1 = CacheControl::from_value("public").unwrap();
+        assert_eq!(
+            test1.heuristic_freshness(last_modified, date, 0.1),
+            Some(Duration::new(3600, 0))
+        );
+        assert_eq!(test1.heuristic_freshness(date, last_modified, 0.1), None);
+        assert_eq!(test1.heuristic_freshness(last_modified, date, -0.1), None);
+
+        let test2 = CacheControl::from_value("public, max-age=60").unwrap();
+        assert_eq!(test2.heuristic_freshness(last_modified, date, 0.1), None);
+        let test3 = CacheControl::from_value("s-maxage=60").unwrap();
+        assert_eq!(test3.heuristic_freshness(last_modified, date, 0.1), None);
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_from_header_map() {
+        use http::header::{HeaderMap, HeaderValue, CACHE_CONTROL};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(CacheControl::from_header_map(&headers), None);
+
+        headers.append(CACHE_CONTROL, HeaderValue::from_static("public"));
+        assert_eq!(
+            CacheControl::from_header_map(&headers).unwrap(),
+            CacheControl::from_value("public").unwrap()
+        );
+
+        headers.append(CACHE_CONTROL, HeaderValue::from_static("max-age=60"));
+        assert_eq!(
+            CacheControl::from_header_map(&headers).unwrap(),
+            CacheControl::from_value("public, max-age=60").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_cache_empty_value() {
+        assert_eq!(CacheControl::from_value("no-cache="), None);
+        assert_eq!(
+            CacheControl::parse("no-cache=", ParseMode::Strict),
+            Err(ParseError::InvalidValue(String::from("no-cache")))
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("no-cache=", ParseMode::Lenient).unwrap(),
+            CacheControl::from_value("no-cache").unwrap()
+        );
+        assert_eq!(
+            CacheControl::from_value("no-cache=\"\"").unwrap(),
+            CacheControl::from_value("no-cache").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_response_directives_only() {
+        let test1 = CacheControl::from_value(
+            "only-if-cached, max-age=60, max-stale=30, min-fresh=10, no-transform",
+        )
+        .unwrap();
+        assert_eq!(
+            test1.response_directives_only(),
+            CacheControl::from_value("max-age=60, no-transform").unwrap()
+        );
+
+        let test2 = CacheControl::from_value("public, max-age=60, max-stale=30").unwrap();
+        assert_eq!(
+            test2.response_directives_only(),
+            CacheControl::from_value("public, max-age=60").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_newline_separators() {
+        assert_eq!(
+            CacheControl::from_value_with_mode("max-age=60\nno-store", ParseMode::Strict),
+            None
+        );
+        let test1 =
+            CacheControl::from_value_with_mode("max-age=60\nno-store", ParseMode::Lenient).unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert!(test1.no_store);
+
+        let test2 =
+            CacheControl::from_value_with_mode("public\r\nmax-age=60", ParseMode::Lenient).unwrap();
+        assert_eq!(
+            test2,
+            CacheControl::from_value("public, max-age=60").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_value_rfc_capped() {
+        let cap = Duration::new(2_147_483_647, 0);
+        assert_eq!(
+            CacheControl::from_value_rfc_capped("max-age=60")
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            CacheControl::from_value_rfc_capped("max-age=2147483648")
+                .unwrap()
+                .max_age,
+            Some(cap)
+        );
+        assert_eq!(
+            CacheControl::from_value_rfc_capped("s-maxage=99999999999999999999999")
+                .unwrap()
+                .s_max_age,
+            Some(cap)
+        );
+        assert_eq!(
+            CacheControl::from_value("s-maxage=99999999999999999999999"),
+            None
+        );
+        assert_eq!(CacheControl::from_value_rfc_capped("max-age=-1"), None);
+        assert_eq!(require_capped_seconds(None, 10), Err(()));
+        assert_eq!(
+            require_capped_seconds(Some("11"), 10),
+            Ok(Duration::new(10, 0))
+        );
+    }
+
+    #[test]
+    fn test_whitespace_only_seconds() {
+        assert_eq!(require_seconds(Some("")), Err(()));
+        assert_eq!(CacheControl::from_value("max-age=   "), None);
+        assert_eq!(
+            CacheControl::parse("max-age=   ", ParseMode::Lenient),
+            Err(ParseError::InvalidValue(String::from("max-age")))
+        );
+        for directive in &[
+            "s-maxage",
+            "max-stale",
+            "min-fresh",
+            "stale-while-revalidate",
+            "stale-if-error",
+        ] {
+            let value = format!("public, {}=   ", directive);
+            assert_eq!(CacheControl::from_value(&value), None);
+            assert_eq!(CacheControl::from_value_rfc_capped(&value), None);
+        }
+    }
+
+    #[test]
+    fn test_split_for_edge() {
+        let test1 = CacheControl::from_value("public, max-age=60, s-maxage=3600, proxy-revalidate")
+            .unwrap();
+        let (edge, browser) = test1.split_for_edge();
+        assert_eq!(edge.to_string(), "public, s-maxage=3600, proxy-revalidate");
+        assert_eq!(browser.to_string(), "public, max-age=60");
+
+        let test2 = CacheControl::from_value("public, max-age=60").unwrap();
+        let (edge, browser) = test2.split_for_edge();
+        assert_eq!(edge.to_string(), "public, s-maxage=60");
+        assert_eq!(browser, test2);
+    }
+
+    #[test]
+    fn test_core_eq() {
+        let test1 = CacheControl::from_value("public, max-age=60, stale-if-error=60").unwrap();
+        let test2 = CacheControl::from_value("public, max-age=60, stale-if-error=600").unwrap();
+        assert!(test1.core_eq(&test2));
+        assert_ne!(test1, test2);
+
+        let test3 = CacheControl::from_value("public, max-age=600, stale-if-error=60").unwrap();
+        assert!(!test1.core_eq(&test3));
+    }
+
+    #[test]
+    fn test_leading_bom() {
+        assert_eq!(
+            CacheControl::from_value_with_mode("\u{feff}max-age=60", ParseMode::Strict)
+                .unwrap()
+                .max_age,
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("\u{feff}max-age=60", ParseMode::Lenient)
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            CacheControl::from_value_with_mode("\u{a0}\u{feff} public", ParseMode::Lenient)
+                .unwrap()
+                .cachability,
+            Some(Cachability::Public)
+        );
+        // `trim` already handles Unicode whitespace such as a non-breaking space.
+        assert_eq!(
+            CacheControl::from_value("\u{a0}public")
+                .unwrap()
+                .cachability,
+            Some(Cachability::Public)
+        );
+    }
+
+    #[test]
+    fn test_is_cacheable_by_role() {
+        let test1 = CacheControl::from_value("private, max-age=60").unwrap();
+        assert!(test1.is_private_cacheable());
+        assert!(!test1.is_shared_cacheable());
+
+        let test2 = CacheControl::from_value("no-store").unwrap();
+        assert!(!test2.is_private_cacheable());
+        assert!(!test2.is_shared_cacheable());
+
+        let test3 = CacheControl::from_value("public").unwrap();
+        assert!(test3.is_private_cacheable());
+        assert!(test3.is_shared_cacheable());
+
+        let test4 = CacheControl::from_value("no-cache").unwrap();
+        assert!(test4.is_private_cacheable());
+        assert!(test4.is_shared_cacheable());
+    }
+
+    #[test]
+    fn test_shared_revalidation_policy() {
+        let policy = |value| {
+            CacheControl::from_value(value)
+                .unwrap()
+                .shared_revalidation_policy()
+        };
+        assert_eq!(policy("public"), SharedRevalidation::NeverStale);
+        assert_eq!(
+            policy("max-age=60, s-maxage=600"),
+            SharedRevalidation::RevalidateAfter(Duration::new(600, 0))
+        );
+        assert_eq!(
+            policy("max-age=60, proxy-revalidate"),
+            SharedRevalidation::RevalidateAfter(Duration::new(60, 0))
+        );
+        assert_eq!(policy("proxy-revalidate"), SharedRevalidation::NeverStale);
+        assert_eq!(policy("must-revalidate"), SharedRevalidation::NeverStale);
+        assert_eq!(policy("s-maxage=0"), SharedRevalidation::AlwaysRevalidate);
+        assert_eq!(
+            policy("no-cache, s-maxage=600"),
+            SharedRevalidation::AlwaysRevalidate
+        );
+    }
+
+    #[test]
+    fn test_to_string_sorted() {
+        let test1 = CacheControl::from_value(
+            "public, max-age=60, s-maxage=600, no-transform, must-revalidate, stale-if-error=5",
+        )
+        .unwrap();
+        assert_eq!(
+            test1.to_string_sorted(),
+            "max-age=60, must-revalidate, no-transform, public, s-maxage=600, stale-if-error=5"
+        );
+        assert_eq!(CacheControl::default().to_string_sorted(), "");
+    }
+
+    #[test]
+    fn test_request_from_value() {
+        let test1 =
+            RequestCacheControl::from_value("no-cache, only-if-cached, min-fresh=5").unwrap();
+        assert!(test1.no_cache);
+        assert!(test1.only_if_cached);
+        assert_eq!(test1.min_fresh, Some(Duration::new(5, 0)));
+
+        let test2 = RequestCacheControl::from_value("max-stale").unwrap();
+        assert_eq!(test2.max_stale, Some(Duration::MAX));
+        let test3 = RequestCacheControl::from_value("max-stale=60").unwrap();
+        assert_eq!(test3.max_stale, Some(Duration::new(60, 0)));
+
+        assert_eq!(RequestCacheControl::from_value("max-age=abc"), None);
+        assert_eq!(
+            RequestCacheControl::from_value("public, immutable").unwrap(),
+            RequestCacheControl::default()
+        );
+    }
+
+    #[test]
+    fn test_forces_origin_fetch() {
+        let forces = |value| {
+            RequestCacheControl::from_value(value)
+                .unwrap()
+                .forces_origin_fetch()
+        };
+        assert!(forces("no-cache"));
+        assert!(forces("max-age=0"));
+        assert!(!forces("max-age=60"));
+        assert!(!forces("max-stale=60"));
+        assert!(!forces(""));
+    }
+
+    #[test]
+    fn test_immutable_meaningful() {
+        let test1 = CacheControl::from_value("immutable").unwrap();
+        assert!(!test1.is_immutable_meaningful());
+        assert_eq!(test1.to_canonical_string(), "");
+        assert_eq!(test1.to_string(), "immutable");
+
+        let test2 = CacheControl::from_value("public, max-age=0, immutable").unwrap();
+        assert!(!test2.is_immutable_meaningful());
+        assert_eq!(test2.to_canonical_string(), "public, max-age=0");
+
+        let test3 = CacheControl::from_value("s-maxage=60, immutable").unwrap();
+        assert!(test3.is_immutable_meaningful());
+        assert_eq!(test3.to_canonical_string(), "s-maxage=60, immutable");
+
+        assert!(!CacheControl::from_value("max-age=60")
+            .unwrap()
+            .is_immutable_meaningful());
+    }
+
+    #[test]
+    fn test_swr_window() {
+        let test1 = CacheControl::from_value("max-age=60, stale-while-revalidate=30").unwrap();
+        assert_eq!(
+            test1.swr_window(false),
+            Some((Duration::new(60, 0), Duration::new(90, 0)))
+        );
+
+        let test2 = CacheControl::from_value("max-age=60").unwrap();
+        assert_eq!(
+            test2.swr_window(false),
+            Some((Duration::new(60, 0), Duration::new(60, 0)))
+        );
+
+        let test3 = CacheControl::from_value("stale-while-revalidate=30").unwrap();
+        assert_eq!(test3.swr_window(false), None);
+
+        let test4 = CacheControl::from_value(
+            "max-age=18446744073709551615, stale-while-revalidate=18446744073709551615",
+        )
+        .unwrap();
+        assert_eq!(test4.swr_window(false).unwrap().1, Duration::MAX);
+
+        let test5 =
+            CacheControl::from_value("max-age=60, must-revalidate, stale-while-revalidate=30")
+                .unwrap();
+        assert_eq!(
+            test5.swr_window(false),
+            Some((Duration::new(60, 0), Duration::new(60, 0)))
+        );
+        let test6 =
+            CacheControl::from_value("max-age=60, proxy-revalidate, stale-while-revalidate=30")
+                .unwrap();
+        assert_eq!(test6.swr_window(false).unwrap().1, Duration::new(90, 0));
+        assert_eq!(test6.swr_window(true).unwrap().1, Duration::new(60, 0));
+    }
+
+    #[test]
+    fn test_trailing_line_ending() {
+        assert_eq!(
+            CacheControl::from_value("max-age=60\r\n").unwrap(),
+            CacheControl::from_value("max-age=60").unwrap()
+        );
+        assert!(
+            CacheControl::from_value("public, no-store\r")
+                .unwrap()
+                .no_store
+        );
+        assert_eq!(
+            CacheControl::from_header("Cache-Control: max-age=60\r\n")
+                .unwrap()
+                .max_age,
+            Some(Duration::new(60, 0))
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_max_age_time() {
+        assert_eq!(
+            CacheControl::from_value("max-age=60")
+                .unwrap()
+                .max_age_time(),
+            Some(time::Duration::seconds(60))
+        );
+        assert_eq!(CacheControl::default().max_age_time(), None);
+        assert_eq!(
+            CacheControl::from_value("max-age=18446744073709551615")
+                .unwrap()
+                .max_age_time(),
+            Some(time::Duration::MAX)
+        );
+    }
+
+    #[test]
+    fn test_intersect_request() {
+        let response = CacheControl::from_value("public, max-age=600, s-maxage=900").unwrap();
+
+        let request = RequestCacheControl::from_value("max-age=30").unwrap();
+        let test1 = response.intersect_request(&request);
+        assert_eq!(test1.max_age, Some(Duration::new(30, 0)));
+        assert_eq!(test1.s_max_age, Some(Duration::new(30, 0)));
+
+        let request = RequestCacheControl::from_value("max-age=6000, min-fresh=100").unwrap();
+        let test2 = response.intersect_request(&request);
+        assert_eq!(test2.max_age, Some(Duration::new(500, 0)));
+        assert_eq!(test2.s_max_age, Some(Duration::new(800, 0)));
+
+        let request = RequestCacheControl::from_value("no-cache").unwrap();
+        let test3 = response.intersect_request(&request);
+        assert_eq!(test3.cachability, Some(Cachability::NoCache));
+        assert_eq!(test3.max_age, Some(Duration::new(600, 0)));
+
+        let request = RequestCacheControl::from_value("max-age=30").unwrap();
+        let test4 = CacheControl::from_value("public")
+            .unwrap()
+            .intersect_request(&request);
+        assert_eq!(test4.max_age, Some(Duration::new(30, 0)));
+
+        let request = RequestCacheControl::default();
+        assert_eq!(response.intersect_request(&request), response);
+    }
+
+    #[test]
+    fn test_can_serve_from_cache() {
+        let minute = Duration::new(60, 0);
+        let hour = Duration::new(3600, 0);
+
+        let test1 = CacheControl::from_value("max-age=600, must-revalidate").unwrap();
+        assert!(test1.can_serve_from_cache(minute, false));
+        assert!(!test1.can_serve_from_cache(hour, false));
+
+        let test2 = CacheControl::from_value("no-cache, max-age=600").unwrap();
+        assert!(!test2.can_serve_from_cache(minute, false));
+
+        let test3 = CacheControl::from_value("max-age=60, s-maxage=7200").unwrap();
+        assert!(test3.can_serve_from_cache(hour, true));
+        assert!(!test3.can_serve_from_cache(hour, false));
+
+        assert!(!CacheControl::default().can_serve_from_cache(minute, false));
+        let test4 = CacheControl::from_value("no-store, max-age=600").unwrap();
+        assert!(!test4.can_serve_from_cache(minute, false));
+    }
+
+    #[test]
+    fn test_thousands_separator() {
+        // Strict mode rejects what looks like a number split by the comma.
+        assert_eq!(CacheControl::from_value("max-age=1,000"), None);
+        assert_eq!(
+            CacheControl::parse("public, s-maxage=1,000,000", ParseMode::Strict),
+            Err(ParseError::InvalidValue(String::from("s-maxage")))
+        );
+        // Lenient mode keeps the first part and ignores the rest.
+        let report = CacheControl::parse("max-age=1,000", ParseMode::Lenient).unwrap();
+        assert_eq!(report.cache_control.max_age, Some(Duration::new(1, 0)));
+        assert_eq!(report.ignored, vec!["000"]);
+        // Valued tokens are never a continuation.
+        assert_eq!(
+            CacheControl::from_value("max-age=1, 000=x").unwrap(),
             CacheControl {
-                cachability: Some(Cachability::Public),
-                max_age: None,
-                s_max_age: None,
-                max_stale: None,
-                min_fresh: None,
-                must_revalidate: false,
-                proxy_revalidate: false,
-                immutable: false,
-                no_store: false,
-                no_transform: false,
-                stale_while_revalidate: None,
-                stale_if_error: Some(Duration::new(60, 0))
+                extensions: vec![(String::from("000"), Some(String::from("x")))],
+                ..CacheControl::from_value("max-age=1").unwrap()
+            }
+        );
+        let (test1, tail) = CacheControl::parse_prefix("max-age=1, 000=x");
+        assert_eq!(test1, CacheControl::from_value("max-age=1, 000=x").unwrap());
+        assert_eq!(tail, "");
+        assert!(CacheControl::lint("max-age=1, 000=x")
+            .iter()
+            .all(|diagnostic| diagnostic.severity != Severity::Error));
+        // A bare numeric extension is written where it can't be mistaken for a continuation.
+        let test2 = CacheControl::from_value("000, stale-if-error=5").unwrap();
+        assert_eq!(test2.to_string(), "000, stale-if-error=5");
+        assert_eq!(CacheControl::from_value(&test2.to_string()).unwrap(), test2);
+        let test3 = CacheControl::from_value("x-a=1, 000, max-age=5").unwrap();
+        assert_eq!(CacheControl::from_value(&test3.to_string()).unwrap(), test3);
+        // Digits after other directives are merely unknown.
+        assert_eq!(
+            CacheControl::from_value("public, 000").unwrap(),
+            CacheControl {
+                extensions: vec![(String::from("000"), None)],
+                ..CacheControl::from_value("public").unwrap()
             }
         );
+    }
+
+    #[test]
+    fn test_from_typed() {
+        let test1 = CacheControl::from_typed(
+            Some(Cachability::Public),
+            Some(Duration::new(60, 0)),
+            &["immutable", "no-transform", "bogus"],
+        );
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert!(test1.immutable);
+        assert!(test1.no_transform);
+        assert!(!test1.no_store);
+        assert_eq!(
+            test1,
+            CacheControl::from_value("public, max-age=60, immutable, no-transform").unwrap()
+        );
+        assert_eq!(
+            CacheControl::from_typed(None, None, &[]),
+            CacheControl::default()
+        );
+    }
+
+    #[test]
+    fn test_grace_period() {
+        let test1 =
+            CacheControl::from_value("max-age=60, must-revalidate, stale-while-revalidate=30")
+                .unwrap();
+        assert_eq!(test1.grace_period(false), Duration::new(0, 0));
+        let test2 = CacheControl::from_value("max-age=60, must-revalidate").unwrap();
+        assert_eq!(test2.grace_period(false), Duration::new(0, 0));
+
+        let test3 = CacheControl::from_value("max-age=60, stale-while-revalidate=30").unwrap();
+        assert_eq!(test3.grace_period(false), Duration::new(30, 0));
+        assert_eq!(test3.grace_period(true), Duration::new(30, 0));
+
+        let test4 =
+            CacheControl::from_value("max-age=60, proxy-revalidate, stale-while-revalidate=30")
+                .unwrap();
+        assert_eq!(test4.grace_period(false), Duration::new(30, 0));
+        assert_eq!(test4.grace_period(true), Duration::new(0, 0));
+
+        let test5 =
+            CacheControl::from_value("public, s-maxage=60, stale-while-revalidate=30").unwrap();
+        assert_eq!(test5.grace_period(false), Duration::new(30, 0));
+        assert_eq!(test5.grace_period(true), Duration::new(0, 0));
+        assert_eq!(
+            test5.swr_window(true),
+            Some((Duration::new(60, 0), Duration::new(60, 0)))
+        );
+    }
+
+    #[test]
+    fn test_write_to() {
+        let test1 = CacheControl::from_value("public, max-age=60, stale-if-error=30").unwrap();
+        let mut buf = b"Cache-Control: ".to_vec();
+        test1.write_to(&mut buf);
+        assert_eq!(buf, format!("Cache-Control: {}", test1).into_bytes());
+
+        let mut buf = Vec::new();
+        CacheControl::default().write_to(&mut buf);
+        assert!(buf.is_empty());
+
+        let test2 = CacheControl {
+            no_cache_fields: vec![String::from("a\"b"), String::from("c\\d")],
+            ..CacheControl::from_value("no-cache, max-age=0, x-a=1").unwrap()
+        };
+        let mut buf = Vec::new();
+        test2.write_to(&mut buf);
+        assert_eq!(buf, test2.to_string().into_bytes());
+        assert_eq!(
+            test2.to_string(),
+            "no-cache=\"a\\\"b, c\\\\d\", max-age=0, x-a=1"
+        );
+    }
+
+    #[test]
+    fn test_parse_reject_unknown_valued() {
+        let report = CacheControl::parse("public, flag-x", ParseMode::RejectUnknownValued).unwrap();
+        assert_eq!(report.cache_control.cachability, Some(Cachability::Public));
+        assert_eq!(report.ignored, vec![String::from("flag-x")]);
+
+        assert_eq!(
+            CacheControl::parse("flag-x, maxage=5", ParseMode::RejectUnknownValued),
+            Err(ParseError::UnknownDirective(String::from("maxage")))
+        );
+        assert_eq!(
+            CacheControl::parse("max-age=\"60\"", ParseMode::RejectUnknownValued),
+            Err(ParseError::InvalidValue(String::from("max-age")))
+        );
+    }
+
+    #[test]
+    fn test_allows_background_refresh() {
+        let test1 = CacheControl::from_value("max-age=60, stale-while-revalidate=30").unwrap();
+        assert!(test1.allows_background_refresh());
+        let test2 = CacheControl::from_value("no-store, stale-while-revalidate=30").unwrap();
+        assert!(!test2.allows_background_refresh());
+        let test3 = CacheControl::from_value("max-age=60").unwrap();
+        assert!(!test3.allows_background_refresh());
+    }
+
+    #[test]
+    fn test_store_but_revalidate() {
+        let test1 = CacheControl::from_value("no-cache").unwrap();
+        assert!(test1.store_but_revalidate());
+        let test2 = CacheControl::from_value("no-store").unwrap();
+        assert!(!test2.store_but_revalidate());
+        let test3 = CacheControl::from_value("no-cache, no-store").unwrap();
+        assert!(!test3.store_but_revalidate());
+        assert!(!CacheControl::default().store_but_revalidate());
+    }
+
+    #[cfg(feature = "percent-encoding")]
+    #[test]
+    fn test_from_percent_encoded() {
+        assert_eq!(
+            CacheControl::from_percent_encoded("public%2C%20max-age%3D60"),
+            CacheControl::from_value("public, max-age=60")
+        );
+        assert_eq!(
+            CacheControl::from_percent_encoded("no-cache%3D%22Set-Cookie%22"),
+            CacheControl::from_value("no-cache=\"Set-Cookie\"")
+        );
+        assert_eq!(CacheControl::from_percent_encoded("max-age%3"), None);
+        assert_eq!(CacheControl::from_percent_encoded("max-age%zz60"), None);
+        assert_eq!(CacheControl::from_percent_encoded("max-age%FF"), None);
+    }
+
+    #[test]
+    fn test_http_1_0_ignored_directives() {
+        let test1 = CacheControl::from_value(
+            "public, max-age=60, s-maxage=600, immutable, stale-while-revalidate=30, must-revalidate",
+        )
+        .unwrap();
+        assert_eq!(
+            test1.http_1_0_ignored_directives(),
+            vec!["s-maxage", "immutable", "stale-while-revalidate"]
+        );
+        let test2 = CacheControl::from_value("no-cache, no-store, max-age=0").unwrap();
+        assert!(test2.http_1_0_ignored_directives().is_empty());
+    }
+
+    #[test]
+    fn test_from_duration() {
+        let test1: CacheControl = Duration::from_secs(60).into();
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(test1.s_max_age, None);
+        assert_eq!(test1.to_string(), "public, max-age=60");
+    }
+
+    #[test]
+    fn test_lenient_hyphenless_aliases() {
+        assert_eq!(
+            CacheControl::parse("maxage=60", ParseMode::Strict),
+            Err(ParseError::UnknownDirective(String::from("maxage")))
+        );
+        assert_eq!(CacheControl::from_value("maxage=60").unwrap().max_age, None);
+
+        let test1 = CacheControl::from_value_with_mode("maxage=60", ParseMode::Lenient).unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        let test2 =
+            CacheControl::from_value_with_mode("smaxage=600, nocache, nostore", ParseMode::Lenient)
+                .unwrap();
+        assert_eq!(test2.s_max_age, Some(Duration::new(600, 0)));
+        assert_eq!(test2.cachability, Some(Cachability::NoCache));
+        assert!(test2.no_store);
+    }
+
+    #[test]
+    fn test_lint() {
+        let value = "public, max-age=abc, maxage=60, no-store";
+        assert_eq!(
+            CacheControl::lint(value),
+            vec![
+                Diagnostic {
+                    severity: Severity::Error,
+                    message: String::from("invalid value for max-age"),
+                    range: 8..19,
+                },
+                Diagnostic {
+                    severity: Severity::Warning,
+                    message: String::from("unknown directive maxage"),
+                    range: 21..30,
+                },
+                Diagnostic {
+                    severity: Severity::Warning,
+                    message: String::from("public with no-store"),
+                    range: 32..40,
+                },
+            ]
+        );
+        assert_eq!(&value[21..30], "maxage=60");
+        assert!(CacheControl::lint("public, max-age=60").is_empty());
+    }
 
-        let test2 = &CacheControl::from_header("Cache-Control: public, stale-if-error");
-        assert!(test2.is_none());
+    #[test]
+    fn test_requires_cached_only() {
+        let test1 = RequestCacheControl::from_value("only-if-cached").unwrap();
+        assert!(test1.requires_cached_only());
+        let test2 = RequestCacheControl::from_value("no-cache, max-stale").unwrap();
+        assert!(!test2.requires_cached_only());
+    }
 
-        let test3 = &CacheControl::from_header("Cache-Control: public, stale-if-error=abc");
-        assert!(test3.is_none());
+    #[test]
+    fn test_from_header_quoted_extension() {
+        let test1 = CacheControl::from_header("Cache-Control: public, x-allow=\"a,b\"").unwrap();
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert_eq!(
+            test1.extensions,
+            vec![(String::from("x-allow"), Some(String::from("\"a,b\"")))]
+        );
+
+        let test2 = CacheControl::from_header(
+            "Cache-Control: public, x-allow=\"a:b, max-age=1\",\r\n max-age=60",
+        )
+        .unwrap();
+        assert_eq!(test2.cachability, Some(Cachability::Public));
+        assert_eq!(test2.max_age, Some(Duration::new(60, 0)));
+
+        let test3 =
+            CacheControl::from_header("Cache-Control: no-cache=\"Set-Cookie, Authorization\"")
+                .unwrap();
+        assert_eq!(
+            test3.no_cache_fields,
+            vec![String::from("Set-Cookie"), String::from("Authorization")]
+        );
+    }
+
+    #[test]
+    fn test_into_builder() {
+        let test1 = CacheControl::from_value("public, max-age=60, must-revalidate").unwrap();
+        let test2 = test1
+            .clone()
+            .into_builder()
+            .max_age(Duration::new(600, 0))
+            .build();
+        assert_eq!(test2.max_age, Some(Duration::new(600, 0)));
+        assert_eq!(test2.to_string(), "public, max-age=600, must-revalidate");
+        assert_eq!(
+            test1.into_builder().build().max_age,
+            Some(Duration::new(60, 0))
+        );
+
+        let test3 = CacheControl::from_value("no-cache=\"Set-Cookie\"").unwrap();
+        assert_eq!(
+            test3.clone().into_builder().no_cache().build().to_string(),
+            "no-cache"
+        );
+        assert_eq!(
+            test3.clone().into_builder().public().build().to_string(),
+            "public"
+        );
+        assert!(test3
+            .clone()
+            .into_builder()
+            .private()
+            .build()
+            .no_cache_fields
+            .is_empty());
+        assert!(test3
+            .clone()
+            .into_builder()
+            .only_if_cached()
+            .build()
+            .no_cache_fields
+            .is_empty());
+        assert!(test3
+            .into_builder()
+            .cachability(Cachability::NoCache)
+            .build()
+            .no_cache_fields
+            .is_empty());
+    }
+
+    #[test]
+    fn test_extensions() {
+        let test1 = CacheControl::from_value(
+            "max-age=60, target-a=\"x, y\", public, flag-b, target-c = 5, no-store",
+        )
+        .unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(60, 0)));
+        assert_eq!(test1.cachability, Some(Cachability::Public));
+        assert!(test1.no_store);
+        assert_eq!(
+            test1.extensions,
+            vec![
+                (String::from("target-a"), Some(String::from("\"x, y\""))),
+                (String::from("flag-b"), None),
+                (String::from("target-c"), Some(String::from("5"))),
+            ]
+        );
+        assert_eq!(
+            test1.to_string(),
+            "public, max-age=60, no-store, target-a=\"x, y\", flag-b, target-c=5"
+        );
+        assert_eq!(CacheControl::from_value(&test1.to_string()), Some(test1));
+    }
+
+    #[test]
+    fn test_reuse_decision() {
+        let test1 = CacheControl::from_value(
+            "public, max-age=60, stale-while-revalidate=30, stale-if-error=600",
+        )
+        .unwrap();
+        assert_eq!(
+            test1.reuse_decision(Duration::new(10, 0), false, false),
+            ReuseDecision::Fresh
+        );
+        assert_eq!(
+            test1.reuse_decision(Duration::new(70, 0), false, false),
+            ReuseDecision::ServeStaleWhileRevalidate
+        );
+        assert_eq!(
+            test1.reuse_decision(Duration::new(100, 0), false, false),
+            ReuseDecision::StaleRevalidate
+        );
+        assert_eq!(
+            test1.reuse_decision(Duration::new(100, 0), false, true),
+            ReuseDecision::ServeStaleOnError
+        );
+        assert_eq!(
+            test1.reuse_decision(Duration::new(700, 0), false, true),
+            ReuseDecision::StaleRevalidate
+        );
+
+        let test2 =
+            CacheControl::from_value("max-age=60, must-revalidate, stale-if-error=600").unwrap();
+        assert_eq!(
+            test2.reuse_decision(Duration::new(100, 0), false, true),
+            ReuseDecision::StaleRevalidate
+        );
+
+        let test3 = CacheControl::from_value("no-cache, max-age=60").unwrap();
+        assert_eq!(
+            test3.reuse_decision(Duration::new(10, 0), false, false),
+            ReuseDecision::StaleRevalidate
+        );
+
+        let test4 = CacheControl::from_value("no-store, max-age=60").unwrap();
+        assert_eq!(
+            test4.reuse_decision(Duration::new(10, 0), false, false),
+            ReuseDecision::MustNotReuse
+        );
+        let test5 = CacheControl::from_value("private, max-age=60").unwrap();
+        assert_eq!(
+            test5.reuse_decision(Duration::new(10, 0), false, false),
+            ReuseDecision::Fresh
+        );
+        assert_eq!(
+            test5.reuse_decision(Duration::new(10, 0), true, false),
+            ReuseDecision::MustNotReuse
+        );
+
+        let test6 =
+            CacheControl::from_value("s-maxage=60, stale-while-revalidate=30, stale-if-error=300")
+                .unwrap();
+        assert_eq!(
+            test6.reuse_decision(Duration::new(70, 0), true, false),
+            ReuseDecision::StaleRevalidate
+        );
+        assert_eq!(
+            test6.reuse_decision(Duration::new(70, 0), true, true),
+            ReuseDecision::StaleRevalidate
+        );
+        assert_eq!(
+            test6.reuse_decision(Duration::new(10, 0), false, true),
+            ReuseDecision::ServeStaleOnError
+        );
+    }
+
+    #[test]
+    fn test_directives_sharing_a_prefix() {
+        let test1 = CacheControl::from_value(
+            "max-age=1, max-stale=2, stale-while-revalidate=3, stale-if-error=4",
+        )
+        .unwrap();
+        assert_eq!(test1.max_age, Some(Duration::new(1, 0)));
+        assert_eq!(test1.max_stale, Some(Duration::new(2, 0)));
+        assert_eq!(test1.stale_while_revalidate, Some(Duration::new(3, 0)));
+        assert_eq!(test1.stale_if_error, Some(Duration::new(4, 0)));
+        assert!(test1.extensions.is_empty());
+
+        let test2 = CacheControl::from_value("max-stale=2, stale-if-error=4").unwrap();
+        assert_eq!(test2.max_age, None);
+        assert_eq!(test2.stale_while_revalidate, None);
+
+        // Truncated or lengthened names are unknown directives, not prefixes of known ones.
+        let test3 = CacheControl::from_value("max=1, max-ages=2, stale=3, stale-if=4").unwrap();
+        assert_eq!(test3.max_age, None);
+        assert_eq!(test3.max_stale, None);
+        assert_eq!(test3.stale_while_revalidate, None);
+        assert_eq!(test3.stale_if_error, None);
+        assert_eq!(test3.extensions.len(), 4);
+    }
+
+    #[test]
+    fn test_is_keyed_per_user() {
+        assert!(CacheControl::from_value("private, max-age=60")
+            .unwrap()
+            .is_keyed_per_user());
+        assert!(CacheControl::from_value("no-cache=\"Authorization\"")
+            .unwrap()
+            .is_keyed_per_user());
+        assert!(CacheControl::from_value("no-cache=\"set-cookie, x-foo\"")
+            .unwrap()
+            .is_keyed_per_user());
+        assert!(!CacheControl::from_value("no-cache=\"x-foo\"")
+            .unwrap()
+            .is_keyed_per_user());
+        assert!(!CacheControl::from_value("no-cache")
+            .unwrap()
+            .is_keyed_per_user());
+        assert!(!CacheControl::from_value("public, max-age=60")
+            .unwrap()
+            .is_keyed_per_user());
+    }
+
+    #[test]
+    fn test_allows_request_collapsing() {
+        let test1 = CacheControl::from_value("public, max-age=60").unwrap();
+        assert!(test1.allows_request_collapsing());
+        let test2 = CacheControl::from_value("private").unwrap();
+        assert!(!test2.allows_request_collapsing());
+        let test3 = CacheControl::from_value("no-cache").unwrap();
+        assert!(!test3.allows_request_collapsing());
+        let test4 = CacheControl::from_value("max-age=60, no-store").unwrap();
+        assert!(!test4.allows_request_collapsing());
+    }
+
+    #[test]
+    fn test_from_value_limited() {
+        assert_eq!(
+            CacheControl::from_value_limited("public, max-age=60, no-transform", 3),
+            CacheControl::from_value("public, max-age=60, no-transform")
+        );
+        assert_eq!(
+            CacheControl::from_value_limited("public, max-age=60, no-transform, x-a", 3),
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_limited(&["x-a"; 1000].join(", "), 16),
+            None
+        );
+        assert_eq!(
+            CacheControl::from_value_limited("public,,, ,", 1),
+            CacheControl::from_value("public")
+        );
+        assert_eq!(
+            CacheControl::from_value_limited("public, max-age=60, x-a", usize::MAX),
+            CacheControl::from_value("public, max-age=60, x-a")
+        );
+    }
+
+    #[test]
+    fn test_to_curl_header_arg() {
+        let test1 = CacheControl::from_value("public, max-age=60").unwrap();
+        assert_eq!(
+            test1.to_curl_header_arg(),
+            "-H 'Cache-Control: public, max-age=60'"
+        );
+        let test2 = CacheControl::from_value("no-cache=\"Set-Cookie\"").unwrap();
+        assert_eq!(
+            test2.to_curl_header_arg(),
+            "-H 'Cache-Control: no-cache=\"Set-Cookie\"'"
+        );
+        let test3 = CacheControl::from_value("public, x-a=\"$(id)`id`'\"").unwrap();
+        assert_eq!(
+            test3.to_curl_header_arg(),
+            "-H 'Cache-Control: public, x-a=\"$(id)`id`'\\''\"'"
+        );
+    }
+
+    #[test]
+    fn test_parse_duplicates() {
+        assert_eq!(
+            CacheControl::from_value("max-age=60, max-age=120")
+                .unwrap()
+                .max_age,
+            Some(Duration::new(120, 0))
+        );
+        assert_eq!(
+            CacheControl::parse("max-age=60, max-age=120", ParseMode::Strict),
+            Err(ParseError::DuplicateDirective(String::from("max-age")))
+        );
+        assert_eq!(
+            CacheControl::parse("no-store, public, no-store", ParseMode::RejectUnknownValued),
+            Err(ParseError::DuplicateDirective(String::from("no-store")))
+        );
+
+        let report = CacheControl::parse(
+            "max-age=60, max_age=120, max-age=1, public, private",
+            ParseMode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(report.cache_control.max_age, Some(Duration::new(1, 0)));
+        assert_eq!(report.duplicates, vec![String::from("max-age")]);
+
+        let report =
+            CacheControl::parse("public, max-age=60, x-a, x-a", ParseMode::Lenient).unwrap();
+        assert!(report.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_sensitive() {
+        let test1 = CacheControl::sensitive();
+        assert_eq!(test1.to_string(), "private, no-store");
+        assert!(!test1.is_cacheable());
+        assert!(!test1.is_shared_cacheable());
+        assert!(!test1.is_private_cacheable());
+
+        assert!(CacheControl::from_value("private").unwrap().is_cacheable());
+        assert!(CacheControl::default().is_cacheable());
+    }
+
+    #[test]
+    fn test_diff() {
+        let test1 = CacheControl::from_value("public, max-age=60, x-a").unwrap();
+        let test2 = CacheControl::from_value("public, max-age=120, no-store, x-b").unwrap();
+        assert_eq!(
+            test1.diff(&test2),
+            vec![
+                FieldChange {
+                    directive: "max-age",
+                    old: Some(String::from("max-age=60")),
+                    new: Some(String::from("max-age=120")),
+                },
+                FieldChange {
+                    directive: "no-store",
+                    old: None,
+                    new: Some(String::from("no-store")),
+                },
+            ]
+        );
+        assert_eq!(test2.diff(&test1)[1].old, Some(String::from("no-store")));
+        assert!(test1.diff(&test1).is_empty());
+    }
+
+    #[test]
+    fn test_boolean_directive_with_value() {
+        assert_eq!(CacheControl::from_value("must-revalidate=1"), None);
+        assert_eq!(
+            CacheControl::parse("max-age=60, must-revalidate=1", ParseMode::Strict),
+            Err(ParseError::InvalidValue(String::from("must-revalidate")))
+        );
+        assert_eq!(CacheControl::from_value("public, no-store=\"\""), None);
+
+        let test1 =
+            CacheControl::from_value_with_mode("must-revalidate=1", ParseMode::Lenient).unwrap();
+        assert!(test1.must_revalidate);
+        assert_eq!(test1, CacheControl::from_value("must-revalidate").unwrap());
+
+        // `private` may list field names, like `no-cache`.
+        let test2 = CacheControl::from_value("private=\"Set-Cookie\"").unwrap();
+        assert_eq!(test2.cachability, Some(Cachability::Private));
+    }
+
+    #[test]
+    fn test_from_header_asymmetric_whitespace() {
+        let expected = CacheControl::from_value("max-age=60");
+        assert_eq!(
+            CacheControl::from_header("Cache-Control :max-age=60"),
+            expected
+        );
+        assert_eq!(
+            CacheControl::from_header(" Cache-Control: max-age=60"),
+            expected
+        );
+        assert_eq!(
+            CacheControl::from_header("Cache-Control:max-age=60 "),
+            expected
+        );
+        assert_eq!(
+            CacheControl::from_header("\tCache-Control\t:max-age=60"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_browser_max_age() {
+        let test1 = CacheControl::from_value("max-age=60, s-maxage=3600").unwrap();
+        assert_eq!(test1.browser_max_age(), Some(Duration::new(60, 0)));
+        let test2 = CacheControl::from_value("public, s-maxage=3600").unwrap();
+        assert_eq!(test2.browser_max_age(), None);
+    }
+
+    #[test]
+    fn test_from_value_or_default() {
+        assert_eq!(
+            CacheControl::from_value_or_default("public, max-age=60"),
+            CacheControl::from_value("public, max-age=60").unwrap()
+        );
+        assert_eq!(
+            CacheControl::from_value_or_default("public, max-age=abc"),
+            CacheControl::default()
+        );
+    }
+
+    #[test]
+    fn test_imposes_no_constraints() {
+        assert!(CacheControl::default().imposes_no_constraints());
+        assert!(CacheControl::from_value(", x-a=1")
+            .unwrap()
+            .imposes_no_constraints());
+        assert!(!CacheControl::from_value("max-age=60")
+            .unwrap()
+            .imposes_no_constraints());
+        assert!(!CacheControl::from_value("no-transform")
+            .unwrap()
+            .imposes_no_constraints());
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        assert_eq!(
+            CacheControl::from_value("max-age=0060").unwrap().max_age,
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            CacheControl::from_value("s-maxage=000").unwrap().s_max_age,
+            Some(Duration::new(0, 0))
+        );
+        assert_eq!(
+            CacheControl::lint("public, max-age=0060"),
+            vec![Diagnostic {
+                severity: Severity::Warning,
+                message: String::from("leading zeros in max-age"),
+                range: 8..20,
+            }]
+        );
+        assert!(CacheControl::lint("max-age=0, s-maxage=60").is_empty());
+    }
+
+    #[test]
+    fn test_caches_disagree() {
+        let test1 = CacheControl::from_value("max-age=60, s-maxage=3600").unwrap();
+        assert!(test1.caches_disagree());
+        let test2 = CacheControl::from_value("private, max-age=60").unwrap();
+        assert!(test2.caches_disagree());
+
+        let test3 = CacheControl::from_value("public, max-age=60, s-maxage=60").unwrap();
+        assert!(!test3.caches_disagree());
+        let test4 = CacheControl::from_value("max-age=60").unwrap();
+        assert!(!test4.caches_disagree());
+    }
+
+    #[test]
+    fn test_builder_cachability() {
+        for &shared in [true, false].iter() {
+            let cachability = if shared {
+                Cachability::Public
+            } else {
+                Cachability::Private
+            };
+            let test1 = CacheControl::builder()
+                .cachability(cachability.clone())
+                .max_age(Duration::new(60, 0))
+                .build();
+            assert_eq!(test1.cachability, Some(cachability));
+            assert_eq!(test1.is_shared_cacheable(), shared);
+        }
+        assert_eq!(
+            CacheControl::builder()
+                .cachability(Cachability::NoCache)
+                .build(),
+            CacheControl::builder().no_cache().build()
+        );
+    }
+
+    #[test]
+    fn test_staleness() {
+        let test1 = CacheControl::from_value("max-age=60, s-maxage=600").unwrap();
+        assert_eq!(
+            test1.staleness(Duration::new(30, 0), false),
+            Duration::new(0, 0)
+        );
+        assert_eq!(
+            test1.staleness(Duration::new(60, 0), false),
+            Duration::new(0, 0)
+        );
+        assert_eq!(
+            test1.staleness(Duration::new(61, 0), false),
+            Duration::new(1, 0)
+        );
+        assert_eq!(
+            test1.staleness(Duration::new(86400, 0), false),
+            Duration::new(86340, 0)
+        );
+        assert_eq!(
+            test1.staleness(Duration::new(610, 0), true),
+            Duration::new(10, 0)
+        );
+
+        let test2 = CacheControl::from_value("public").unwrap();
+        assert_eq!(
+            test2.staleness(Duration::new(86400, 0), false),
+            Duration::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_from_headers_block() {
+        let test1 = CacheControl::from_headers_block(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/html\r\n\
+             cache-control: public\r\n\
+             this line has no colon\r\n\
+             CACHE-CONTROL: max-age=60,\r\n\
+             \tmust-revalidate\r\n\
+             \r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            test1,
+            CacheControl::from_value("public, max-age=60, must-revalidate").unwrap()
+        );
+        assert_eq!(
+            CacheControl::from_headers_block("no colon\nContent-Length: 0\n"),
+            None
+        );
+        assert_eq!(CacheControl::from_headers_block(""), None);
+    }
+
+    #[test]
+    fn test_for_304_response() {
+        let test1 = CacheControl::from_value(
+            "public, max-age=60, min-fresh=10, no-store, no-transform, stale-if-error=600, x-a",
+        )
+        .unwrap();
+        assert_eq!(
+            test1.for_304_response(),
+            "public, max-age=60, stale-if-error=600"
+        );
+        let test2 = CacheControl::from_value("no-cache, must-revalidate").unwrap();
+        assert_eq!(test2.for_304_response(), "no-cache, must-revalidate");
+    }
+
+    #[test]
+    fn test_conflicts_immutable_no_store() {
+        let test1 = CacheControl::from_value("immutable, no-store").unwrap();
+        assert_eq!(
+            test1.conflicts(),
+            vec![ConflictError {
+                first: "immutable",
+                second: "no-store"
+            }]
+        );
+        assert_eq!(
+            test1.validate().unwrap_err().to_string(),
+            "immutable with no-store"
+        );
+    }
+
+    #[test]
+    fn test_extended_serveable_age() {
+        let max_stale = Some(Duration::new(120, 0));
+        let test1 = CacheControl::from_value("max-age=60").unwrap();
+        assert_eq!(
+            test1.extended_serveable_age(max_stale, true),
+            Some(Duration::new(180, 0))
+        );
+        assert_eq!(
+            test1.extended_serveable_age(None, true),
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            test1.extended_serveable_age(Some(Duration::MAX), true),
+            Some(Duration::MAX)
+        );
+
+        let test2 = CacheControl::from_value("max-age=60, must-revalidate").unwrap();
+        assert_eq!(
+            test2.extended_serveable_age(max_stale, true),
+            Some(Duration::new(60, 0))
+        );
+        let test3 = CacheControl::from_value("max-age=60, proxy-revalidate").unwrap();
+        assert_eq!(
+            test3.extended_serveable_age(max_stale, false),
+            Some(Duration::new(180, 0))
+        );
+        assert_eq!(
+            test3.extended_serveable_age(max_stale, true),
+            Some(Duration::new(60, 0))
+        );
+
+        let test4 = CacheControl::from_value("public, s-maxage=60").unwrap();
+        let test5 = CacheControl::from_value("public, s-maxage=60, proxy-revalidate").unwrap();
+        assert!(test4.behaves_same_for(&test5, CacheRole::Shared));
+        assert_eq!(
+            test4.extended_serveable_age(max_stale, true),
+            Some(Duration::new(60, 0))
+        );
+        assert_eq!(
+            test4.extended_serveable_age(max_stale, true),
+            test5.extended_serveable_age(max_stale, true)
+        );
+        assert_eq!(test4.extended_serveable_age(max_stale, false), None);
+        assert_eq!(
+            CacheControl::default().extended_serveable_age(max_stale, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_tokens() {
+        assert_eq!(
+            CacheControl::from_tokens(["public", "max-age=60"].iter()),
+            CacheControl::from_value("public, max-age=60")
+        );
+        let test1 = vec![String::from("no-store"), String::from(" x-a = 1 ")];
+        assert_eq!(
+            CacheControl::from_tokens(&test1),
+            CacheControl::from_value("no-store, x-a=1")
+        );
+        assert_eq!(CacheControl::from_tokens(["max-age=abc"].iter()), None);
+    }
+
+    #[test]
+    fn test_lint_whole_value() {
+        let value = "public, max-age=1,000, no-transform";
+        assert_eq!(
+            CacheControl::parse(value, ParseMode::Strict),
+            Err(ParseError::InvalidValue(String::from("max-age")))
+        );
+        assert_eq!(
+            CacheControl::lint(value),
+            vec![Diagnostic {
+                severity: Severity::Error,
+                message: String::from("invalid value for max-age"),
+                range: 8..21,
+            }]
+        );
+        assert_eq!(&value[8..21], "max-age=1,000");
+
+        assert_eq!(
+            CacheControl::lint("s-maxage=1,000,000, x-a, max-age=abc"),
+            vec![
+                Diagnostic {
+                    severity: Severity::Error,
+                    message: String::from("invalid value for s-maxage"),
+                    range: 0..18,
+                },
+                Diagnostic {
+                    severity: Severity::Warning,
+                    message: String::from("unknown directive x-a"),
+                    range: 20..23,
+                },
+                Diagnostic {
+                    severity: Severity::Error,
+                    message: String::from("invalid value for max-age"),
+                    range: 25..36,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_duplicates() {
+        let value = "max-age=60, public, max-age=120";
+        assert_eq!(
+            CacheControl::lint(value),
+            vec![Diagnostic {
+                severity: Severity::Warning,
+                message: String::from("duplicate directive max-age"),
+                range: 20..31,
+            }]
+        );
+        assert_eq!(&value[20..31], "max-age=120");
+        assert_eq!(CacheControl::lint("max-age=60, max-age=120").len(), 1);
     }
 }
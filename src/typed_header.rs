@@ -0,0 +1,103 @@
+//! Integration with the typed headers of the `headers` crate.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use headers::{Error, Header, HeaderName, HeaderValue};
+
+use super::CacheControl;
+
+/// Wraps a `CacheControl` so it can be used with `headers::HeaderMapExt` and other typed
+/// header extractors.
+/// # Example
+/// ```
+/// extern crate cache_control;
+/// extern crate headers;
+/// extern crate http;
+///
+/// use cache_control::{CacheControl, CacheControlHeader};
+/// use headers::HeaderMapExt;
+///
+/// let mut map = http::HeaderMap::new();
+/// map.typed_insert(CacheControlHeader(CacheControl::static_asset()));
+/// let header: CacheControlHeader = map.typed_get().unwrap();
+/// assert!(header.0.immutable);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct CacheControlHeader(pub CacheControl);
+
+impl Header for CacheControlHeader {
+    fn name() -> &'static HeaderName {
+        &http::header::CACHE_CONTROL
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut parts = Vec::new();
+        for value in values {
+            parts.push(value.to_str().map_err(|_| Error::invalid())?);
+        }
+        if parts.is_empty() {
+            return Err(Error::invalid());
+        }
+        CacheControl::from_value(&parts.join(", "))
+            .map(CacheControlHeader)
+            .ok_or_else(Error::invalid)
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        // Values with characters a header can't carry, such as a line break kept in an extension
+        // or in a `no-cache` field name, make this fail. `Header` has no way to report it, so
+        // nothing is encoded.
+        if let Ok(value) = HeaderValue::from_str(&self.0.to_string()) {
+            values.extend(core::iter::once(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CacheControlHeader;
+    use crate::CacheControl;
+    use headers::{Header, HeaderValue};
+
+    #[test]
+    fn test_decode() {
+        let values = [
+            HeaderValue::from_static("public"),
+            HeaderValue::from_static("max-age=60"),
+        ];
+        assert_eq!(
+            CacheControlHeader::decode(&mut values.iter()).unwrap(),
+            CacheControlHeader(CacheControl::from_value("public, max-age=60").unwrap())
+        );
+        assert!(CacheControlHeader::decode(&mut [].iter()).is_err());
+
+        let values = [HeaderValue::from_static("max-age=abc")];
+        assert!(CacheControlHeader::decode(&mut values.iter()).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut values = Vec::new();
+        CacheControlHeader(CacheControl::static_asset()).encode(&mut values);
+        assert_eq!(
+            values,
+            vec![HeaderValue::from_static(
+                "public, max-age=31536000, immutable"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_encode_invalid() {
+        let mut values = Vec::new();
+        CacheControlHeader(CacheControl::from_value("x-a=1\n2").unwrap()).encode(&mut values);
+        assert!(values.is_empty());
+    }
+}